@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
-    Address, Env, Symbol, Vec, contract, contracterror, contractimpl, contracttype, symbol_short,
-    token,
+    Address, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec, contract,
+    contractclient, contracterror, contractimpl, contracttype, symbol_short, token, xdr::ToXdr,
 };
 
 // Milestone status tracking
@@ -9,8 +9,10 @@ use soroban_sdk::{
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MilestoneStatus {
     Pending,
+    InProgress,
     Released,
     Disputed,
+    Refunded,
 }
 
 // Individual milestone in an escrow
@@ -20,6 +22,11 @@ pub struct Milestone {
     pub amount: i128,
     pub status: MilestoneStatus,
     pub description: Symbol,
+    pub depends_on: Option<u32>, // Earlier milestone that must be released first
+    pub released_at: Option<u64>, // Ledger timestamp of the release to the recipient
+    pub recipient: Option<Address>, // Payee overriding the escrow's recipient
+    pub deadline: Option<u64>,   // Expected delivery timestamp
+    pub metadata_uri: Option<String>, // Off-chain deliverable or spec for this milestone
 }
 
 // Overall escrow status
@@ -32,6 +39,16 @@ pub enum EscrowStatus {
     Cancelled, // Escrow cancelled, funds refunded
 }
 
+// Lifecycle of the bond a recipient posts to accept an escrow
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BondStatus {
+    Unposted,  // No bond held by the contract
+    Posted,    // Bond held until the escrow ends
+    Forfeited, // Bond paid to the depositor after the recipient backed out
+    Returned,  // Bond paid back to the recipient
+}
+
 // Main escrow structure
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -45,6 +62,189 @@ pub struct Escrow {
     pub token: Address,
     pub status: EscrowStatus,
     pub deadline: u64, // NEW: Deadline for escrow completion
+    pub total_refunded: i128,
+    pub arbiter: Option<Address>,
+    pub arbiter_cap: i128, // Most the arbiter may redirect across all disputes
+    pub arbiter_moved: i128, // Amount the arbiter has redirected so far
+    pub created_at: u64,
+    pub private: bool, // Reads restricted to participants via `get_escrow_as`
+    pub verifier: Option<Address>, // Contract that must approve each release
+    pub frozen: bool,  // Set by the admin or arbiter during an investigation
+    pub min_action_interval: u32, // Ledgers required between releases and disputes
+    pub last_action_ledger: Option<u32>,
+    pub recipient_bond: i128, // Bond the recipient must post, forfeited if they cancel
+    pub bond_status: BondStatus,
+    pub category: Symbol, // Platform-defined grouping, e.g. freelance or rental
+    pub recipient_signers: Vec<Address>, // Recipient-side signers approving deliveries
+    pub recipient_threshold: u32, // Approvals needed before confirm_delivery; 0 disables
+    pub release_cooldown: u32, // Minimum ledgers between milestone releases
+    pub last_release_ledger: Option<u32>,
+    pub vesting: VestingSchedule, // Releases by time instead of by milestone
+    pub require_proof: bool,      // Deliveries need a recipient proof before confirmation
+    pub funding_deadline: Option<u64>, // Deposit must land by then or the escrow can expire
+    pub pause_reason: Option<Symbol>, // Set while releases on this escrow are paused
+    pub quiet: bool,              // Skips per-milestone events in batch operations
+    pub recipient_whitelist: Vec<Address>, // Payouts must go to one of these; empty allows any
+    pub callback: Option<Address>, // Notified after each milestone release
+    pub dispute_window: u64, // Seconds a dispute may stay open before its default applies; 0 disables
+    pub dispute_default: DisputeDefault, // Outcome applied to a dispute left open past the window
+    pub token_symbol: Option<Symbol>, // Display symbol of the escrowed token
+    pub token_decimals: Option<u32>, // Display decimals of the escrowed token
+    pub auto_release: bool,  // Single-milestone escrow pays out once a delivery goes unchallenged
+}
+
+// Milestone layout stored before dependencies were tracked
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MilestoneV1 {
+    pub amount: i128,
+    pub status: MilestoneStatus,
+    pub description: Symbol,
+}
+
+// Escrow layout stored before refunds, arbiters and timestamps were tracked.
+// Only read by `migrate_escrow` to upgrade existing entries.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowV1 {
+    pub depositor: Address,
+    pub recipient: Address,
+    pub token_address: Address,
+    pub total_amount: i128,
+    pub total_released: i128,
+    pub milestones: Vec<MilestoneV1>,
+    pub token: Address,
+    pub status: EscrowStatus,
+    pub deadline: u64,
+}
+
+// Dispute raised against a milestone
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeInfo {
+    pub disputer: Address,
+    pub reason: String,
+    pub disputed_at: u64,
+    pub resolved: bool,
+    pub fee: i128,    // Dispute fee paid by the disputer
+    pub upheld: bool, // Whether the resolution went the disputer's way
+}
+
+// A milestone together with its position and the state around it
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MilestoneContext {
+    pub milestone: Milestone,
+    pub index: u32,
+    pub escrow_status: EscrowStatus,
+    pub is_disputed: bool,
+}
+
+// Linear vesting schedule; all times are ledger timestamps in seconds.
+// A zero duration means the escrow does not vest.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VestingSchedule {
+    pub start: u64,    // Vesting begins
+    pub cliff: u64,    // Seconds after start before anything vests
+    pub duration: u64, // Seconds after start until everything has vested
+}
+
+// Part an address plays in an escrow
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EscrowRole {
+    Depositor,
+    Recipient,
+    Arbiter,
+    None,
+}
+
+// Oracle price an escrow's releases are contingent on. With `above` set the
+// price must be at least `threshold`, otherwise at most `threshold`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceCondition {
+    pub oracle: Address,
+    pub asset: Symbol,
+    pub threshold: i128,
+    pub above: bool,
+}
+
+// Side that wins a dispute nobody resolves within the escrow's window
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DisputeDefault {
+    Recipient,
+    Depositor,
+}
+
+// Milestones as they stood just before a milestone was disputed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeSnapshot {
+    pub milestone_index: u32,
+    pub taken_at: u64,
+    pub milestones: Vec<Milestone>,
+}
+
+// Lifecycle event kept in an escrow's on-chain history
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscrowEventRecord {
+    pub sequence: u32, // Per-escrow counter, increasing with every event
+    pub action: Symbol,
+    pub timestamp: u64,
+}
+
+// Distribution of a milestone release, as previewed by `simulate_release`
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleasePlan {
+    pub recipient: Address,
+    pub to_recipient: i128,
+    pub fee: i128,
+}
+
+// Live contract configuration, as returned by `config`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractConfig {
+    pub admin: Option<Address>,
+    pub paused: bool,
+    pub max_milestones: u32,
+    pub fee_bps: i128,
+    pub fee_collector: Option<Address>,
+    pub dispute_fee: i128,
+    pub max_escrow_amount: i128,
+    pub max_tvl: i128,
+    pub min_milestone_amount: i128,
+}
+
+// Complete snapshot of an escrow for off-chain indexers, as returned by `export_escrow`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowExport {
+    pub escrow_id: u64,
+    pub escrow: Escrow,
+    pub milestone_statuses: Vec<MilestoneStatus>,
+    pub funded: bool,                      // The deposit has landed at some point
+    pub balance: i128,                     // Amount the contract still holds for the escrow
+    pub disputes: Vec<(u32, DisputeInfo)>, // Every dispute raised, by milestone index
+    pub exported_at: u64,                  // Ledger timestamp of the snapshot
+}
+
+// Optional subsystems of a deployment, as returned by `features`
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeatureFlags {
+    pub fees: bool,              // Platform fee is charged on releases
+    pub dispute_fees: bool,      // Raising a dispute costs a fee
+    pub creator_allowlist: bool, // Only allowlisted depositors may create escrows
+    pub token_allowlist: bool,   // Only allowlisted tokens may be escrowed
+    pub amount_cap: bool,        // Escrow totals are capped
+    pub tvl_cap: bool,           // Total value locked is capped
+    pub min_milestone: bool,     // Milestones have a minimum amount
 }
 
 // Contract error types
@@ -68,6 +268,64 @@ pub enum Error {
     TokenTransferFailed = 15, // NEW: Token transfer error
     TreasuryNotInitialized = 16,
     InvalidFeeConfiguration = 17,
+    InvalidReason = 18,
+    MilestoneDisputed = 19,
+    ContractPaused = 20,
+    InvalidMilestoneLimit = 21,
+    ArbiterCapExceeded = 22,
+    MilestoneNotDisputed = 23,
+    InvalidArbiterCap = 24,
+    ConditionsNotMet = 25,
+    DependencyNotMet = 26,
+    InvalidDependency = 27,
+    EscrowFrozen = 28,
+    ActionTooSoon = 29,
+    TokenNotAllowed = 30,
+    BondAlreadyPosted = 31,
+    InvalidBondAmount = 32,
+    TotalAmountOverflow = 33,
+    InvalidThreshold = 34,
+    QuorumNotReached = 35,
+    InvalidDisputeFee = 36,
+    TooManyNotes = 37,
+    InvalidNote = 38,
+    AmountExceedsLimit = 39,
+    InvalidVestingSchedule = 40,
+    VestingEscrow = 41,
+    ReentrancyDetected = 42,
+    OverReleased = 43,
+    CreatorNotAllowed = 44,
+    MilestoneAlreadyStarted = 45,
+    TvlCapExceeded = 46,
+    FundingDeadlinePassed = 47,
+    EscrowPaused = 48,
+    NoPendingAdmin = 49,
+    RecipientNotWhitelisted = 50,
+    MilestoneTooSmall = 51,
+    AutoReleaseUnsupported = 52,
+    InvalidMetadataUri = 53,
+    AlreadyInitialized = 54,
+    TooManyOpenRequests = 55,
+    DuplicateReference = 56,
+    AlreadyMigrated = 57,
+}
+
+// Interface for external contracts deciding whether an escrow's release conditions hold
+#[contractclient(name = "VerifierClient")]
+pub trait Verifier {
+    fn check(env: Env, escrow_id: u64) -> bool;
+}
+
+// Interface for price feeds consulted by an escrow's price condition
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn price(env: Env, asset: Symbol) -> i128;
+}
+
+// Interface for contracts notified when an escrow's milestones are released
+#[contractclient(name = "ReleaseListenerClient")]
+pub trait ReleaseListener {
+    fn on_release(env: Env, escrow_id: u64, milestone_index: u32, amount: i128);
 }
 
 // Platform fee configuration (in basis points: 1 bps = 0.01%)
@@ -75,6 +333,43 @@ pub enum Error {
 const DEFAULT_FEE_BPS: i128 = 50;
 const BPS_DENOMINATOR: i128 = 10000;
 
+// Default cap on milestones per escrow, adjustable by the admin
+const DEFAULT_MAX_MILESTONES: u32 = 20;
+
+// Number of lifecycle events retained per escrow
+const MAX_RECENT_EVENTS: u32 = 16;
+
+// Maximum length in bytes of a dispute reason
+const MAX_REASON_LEN: u32 = 64;
+
+// Maximum number of notes kept per escrow
+const MAX_NOTES: u32 = 20;
+
+// Maximum length in bytes of a participant note
+const MAX_NOTE_LEN: u32 = 256;
+
+// Maximum length in bytes of a milestone's metadata URI
+const MAX_URI_LEN: u32 = 256;
+
+// Schedule of escrows that release by milestone rather than by vesting
+const NO_VESTING: VestingSchedule = VestingSchedule {
+    start: 0,
+    cliff: 0,
+    duration: 0,
+};
+
+// Maximum number of ids returned by a single paginated query
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Approximate seconds per ledger, used to turn ledger-based throttles into countdowns
+const LEDGER_SECONDS: u64 = 5;
+
+// Seconds the depositor has to dispute a delivery before it auto-releases
+const AUTO_RELEASE_WINDOW: u64 = 86_400;
+
+// Maximum number of unfunded escrow requests a recipient may have open
+const MAX_OPEN_REQUESTS: u32 = 10;
+
 #[contract]
 pub struct VaultixEscrow;
 
@@ -87,8 +382,17 @@ impl VaultixEscrow {
     /// * `fee_bps` - Optional fee in basis points (default: 50 bps = 0.5%)
     ///
     /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `AlreadyInitialized` - If the contract already has an admin
     /// * `InvalidFeeConfiguration` - If fee_bps exceeds 10000 (100%)
     pub fn initialize(env: Env, treasury: Address, fee_bps: Option<i128>) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        // A second call would silently replace the admin
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+
         // Verify treasury address authorization
         treasury.require_auth();
 
@@ -104,6 +408,11 @@ impl VaultixEscrow {
             .instance()
             .set(&symbol_short!("treasury"), &treasury);
 
+        // The treasury doubles as the initial admin
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &treasury);
+
         // Store fee configuration
         env.storage()
             .instance()
@@ -118,17 +427,14 @@ impl VaultixEscrow {
     /// * `new_fee_bps` - New fee in basis points
     ///
     /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
     /// * `TreasuryNotInitialized` - If contract not initialized
-    /// * `UnauthorizedAccess` - If caller is not treasury
+    /// * `UnauthorizedAccess` - If caller is not the admin
     /// * `InvalidFeeConfiguration` - If fee exceeds 100%
     pub fn update_fee(env: Env, new_fee_bps: i128) -> Result<(), Error> {
-        let treasury: Address = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("treasury"))
-            .ok_or(Error::TreasuryNotInitialized)?;
+        ensure_unlocked(&env)?;
 
-        treasury.require_auth();
+        require_admin(&env)?;
 
         if !(0..=BPS_DENOMINATOR).contains(&new_fee_bps) {
             return Err(Error::InvalidFeeConfiguration);
@@ -141,451 +447,4686 @@ impl VaultixEscrow {
         Ok(())
     }
 
-    /// Returns the current treasury address and fee configuration.
-    pub fn get_config(env: Env) -> Result<(Address, i128), Error> {
-        let treasury: Address = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("treasury"))
-            .ok_or(Error::TreasuryNotInitialized)?;
+    /// Pauses or resumes all escrow mutations (admin only).
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    pub fn set_paused(env: Env, paused: bool) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
 
-        let fee_bps: i128 = env
-            .storage()
+        require_admin(&env)?;
+
+        env.storage()
             .instance()
-            .get(&symbol_short!("fee_bps"))
-            .unwrap_or(DEFAULT_FEE_BPS);
+            .set(&symbol_short!("paused"), &paused);
 
-        Ok((treasury, fee_bps))
+        Ok(())
     }
 
-    /// Creates a new escrow with milestone-based payment releases.
-    /// NOTE: This only creates the escrow structure. Funds must be deposited separately via deposit_funds().
+    /// Proposes a new admin (admin only). The handover completes only once
+    /// the proposed address calls `accept_admin`, so a mistyped address
+    /// cannot lock the contract.
     ///
-    /// # Arguments
-    /// * `escrow_id` - Unique identifier for the escrow
-    /// * `depositor` - Address funding the escrow
-    /// * `recipient` - Address receiving milestone payments
-    /// * `token_address` - Address of the token contract (e.g., XLM, USDC)
-    /// * `milestones` - Vector of milestones defining payment schedule
-    /// * `deadline` - Unix timestamp deadline for escrow completion
-    /// * `token` - Token contract address for payments
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    pub fn propose_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        require_admin(&env)?;
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("pend_adm"), &new_admin);
+
+        Ok(())
+    }
+
+    /// Completes an admin handover proposed with `propose_admin`.
     ///
     /// # Errors
-    /// * `EscrowAlreadyExists` - If escrow_id is already in use
-    /// * `VectorTooLarge` - If more than 20 milestones provided
-    /// * `InvalidMilestoneAmount` - If any milestone amount is zero or negative
-    /// * `SelfDealing` - If depositor and recipient are the same
-    pub fn create_escrow(
-        env: Env,
-        escrow_id: u64,
-        depositor: Address,
-        recipient: Address,
-        token_address: Address,
-        milestones: Vec<Milestone>,
-        deadline: u64,
-    ) -> Result<(), Error> {
-        // Authenticate the depositor
-        depositor.require_auth();
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `NoPendingAdmin` - If no handover has been proposed
+    /// * `UnauthorizedAccess` - If `new_admin` is not the proposed address
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
 
-        // Validate no self-dealing (depositor cannot be recipient)
-        if depositor == recipient {
-            return Err(Error::SelfDealing);
-        }
+        new_admin.require_auth();
 
-        // Check if escrow already exists
-        let storage_key = get_storage_key(escrow_id);
-        if env.storage().persistent().has(&storage_key) {
-            return Err(Error::EscrowAlreadyExists);
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("pend_adm"))
+            .ok_or(Error::NoPendingAdmin)?;
+        if pending != new_admin {
+            return Err(Error::UnauthorizedAccess);
         }
 
-        // Validate milestones and calculate total
-        let total_amount = validate_milestones(&milestones)?;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &new_admin);
+        env.storage().instance().remove(&symbol_short!("pend_adm"));
 
-        // Initialize all milestones to Pending status
-        let mut initialized_milestones = Vec::new(&env);
-        for milestone in milestones.iter() {
-            let mut m = milestone.clone();
-            m.status = MilestoneStatus::Pending;
-            initialized_milestones.push_back(m);
-        }
+        Ok(())
+    }
 
-        // Create the escrow in Created state (not yet funded)
-        let escrow = Escrow {
-            depositor: depositor.clone(),
-            recipient,
-            token_address: token_address.clone(),
-            total_amount,
-            total_released: 0,
-            milestones: initialized_milestones,
-            token: token_address,
-            status: EscrowStatus::Created, // Initially Created, becomes Active after deposit
-            deadline,
-        };
+    /// Returns the admin proposed by `propose_admin` and not yet accepted.
+    pub fn pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("pend_adm"))
+    }
 
-        // Save to persistent storage
-        env.storage().persistent().set(&storage_key, &escrow);
+    /// Updates the maximum number of milestones per escrow (admin only).
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `InvalidMilestoneLimit` - If the limit is zero
+    pub fn set_max_milestones(env: Env, max_milestones: u32) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        require_admin(&env)?;
+
+        if max_milestones == 0 {
+            return Err(Error::InvalidMilestoneLimit);
+        }
 
-        // Extend TTL for long-term storage
         env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+            .instance()
+            .set(&symbol_short!("max_ms"), &max_milestones);
 
         Ok(())
     }
 
-    /// Deposits funds into an escrow, transitioning it from Created to Active.
-    /// The depositor must have approved this contract to spend the required amount.
+    /// Sets the fee a disputer pays, in the escrow's token, to raise a dispute
+    /// (admin only). The fee is refunded if the dispute goes the disputer's way
+    /// and forfeited to the other party otherwise.
     ///
-    /// # Arguments
-    /// * `escrow_id` - Identifier of the escrow to fund
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `InvalidDisputeFee` - If the fee is negative
+    pub fn set_dispute_fee(env: Env, dispute_fee: i128) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        require_admin(&env)?;
+
+        if dispute_fee < 0 {
+            return Err(Error::InvalidDisputeFee);
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("disp_fee"), &dispute_fee);
+
+        Ok(())
+    }
+
+    /// Caps the total amount of any new escrow (admin only).
+    /// Zero removes the cap.
     ///
     /// # Errors
-    /// * `EscrowNotFound` - If escrow doesn't exist
-    /// * `UnauthorizedAccess` - If caller is not the depositor
-    /// * `EscrowAlreadyFunded` - If escrow is already in Active state
-    /// * `TokenTransferFailed` - If token transfer fails
-    pub fn deposit_funds(env: Env, escrow_id: u64) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `InvalidMilestoneAmount` - If the cap is negative
+    pub fn set_max_escrow_amount(env: Env, max_escrow_amount: i128) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
 
-        // Load escrow from storage
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        require_admin(&env)?;
 
-        // Verify authorization - only depositor can fund
-        escrow.depositor.require_auth();
+        if max_escrow_amount < 0 {
+            return Err(Error::InvalidMilestoneAmount);
+        }
 
-        // Check escrow hasn't already been funded
-        if escrow.status != EscrowStatus::Created {
-            return Err(Error::EscrowAlreadyFunded);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("max_amt"), &max_escrow_amount);
+
+        Ok(())
+    }
+
+    /// Caps the total value locked across all funded escrows (admin only).
+    /// Deposits that would exceed it are refused. Zero removes the cap.
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `InvalidMilestoneAmount` - If the cap is negative
+    pub fn set_max_tvl(env: Env, max_tvl: i128) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        require_admin(&env)?;
+
+        if max_tvl < 0 {
+            return Err(Error::InvalidMilestoneAmount);
         }
 
-        // Initialize token client for the specified token
-        let token_client = token::Client::new(&env, &escrow.token_address);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("max_tvl"), &max_tvl);
 
-        // Transfer tokens from depositor to contract
-        // NOTE: Depositor must have approved this contract to spend their tokens
-        token_client.transfer_from(
-            &env.current_contract_address(), // spender (this contract)
-            &escrow.depositor,               // from (depositor's address)
-            &env.current_contract_address(), // to (contract's address - holds in escrow)
-            &escrow.total_amount,            // amount to transfer
-        );
+        Ok(())
+    }
 
-        // Update escrow status to Active
-        escrow.status = EscrowStatus::Active;
+    /// Sets the smallest amount a single milestone may carry (admin only).
+    /// Zero disables the check.
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `InvalidMilestoneAmount` - If the minimum is negative
+    pub fn set_min_milestone_amount(env: Env, min_amount: i128) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
 
-        // Save updated escrow
-        env.storage().persistent().set(&storage_key, &escrow);
+        require_admin(&env)?;
+
+        if min_amount < 0 {
+            return Err(Error::InvalidMilestoneAmount);
+        }
 
-        // Extend TTL
         env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+            .instance()
+            .set(&symbol_short!("min_amt"), &min_amount);
 
         Ok(())
     }
 
-    /// Retrieves escrow details (read-only)
-    pub fn get_escrow(env: Env, escrow_id: u64) -> Result<Escrow, Error> {
-        let storage_key = get_storage_key(escrow_id);
+    /// Returns the platform fees collected in `token` over the contract's
+    /// lifetime. Fees go straight to the treasury, so this is a running
+    /// total rather than a withdrawable balance.
+    pub fn total_fees_collected(env: Env, token: Address) -> i128 {
         env.storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)
+            .instance()
+            .get(&get_fees_key(&token))
+            .unwrap_or(0)
     }
 
-    /// Read-only helper to fetch escrow status
-    pub fn get_state(env: Env, escrow_id: u64) -> Result<EscrowStatus, Error> {
-        let escrow = Self::get_escrow(env, escrow_id)?;
-        Ok(escrow.status)
+    /// Returns the amount deposited into escrows that has not yet been
+    /// released or refunded.
+    pub fn total_value_locked(env: Env) -> i128 {
+        get_tvl(&env)
     }
 
-    /// Releases a specific milestone payment to the recipient with platform fee deduction.
-    ///
-    /// # Arguments
-    /// * `escrow_id` - Identifier of the escrow
-    /// * `milestone_index` - Index of the milestone to release
-    /// * `token_address` - Address of the token contract for transfers
+    /// Adds a token to the allowlist (admin only).
+    /// While the allowlist is empty, escrows may use any token.
     ///
     /// # Errors
-    /// * `EscrowNotFound` - If escrow doesn't exist
-    /// * `UnauthorizedAccess` - If caller is not the depositor
-    /// * `EscrowNotActive` - If escrow is not in Active state
-    /// * `MilestoneNotFound` - If index is out of bounds
-    /// * `MilestoneAlreadyReleased` - If milestone was already released
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
     /// * `TreasuryNotInitialized` - If contract not initialized
-    ///
-    /// # Fee Calculation
-    /// Platform fee is calculated using basis points: fee = (amount * fee_bps) / 10000
-    /// The recipient receives: amount - fee
-    /// The treasury receives: fee
-    pub fn release_milestone(
-        env: Env,
-        escrow_id: u64,
-        milestone_index: u32,
-        token_address: Address,
-    ) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-
-        // Load escrow from storage
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+    pub fn add_allowed_token(env: Env, token: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
 
-        // Verify authorization - only depositor can release funds
-        escrow.depositor.require_auth();
+        require_admin(&env)?;
 
-        // Check escrow is active (funds deposited)
-        if escrow.status != EscrowStatus::Active {
-            return Err(Error::EscrowNotActive);
+        let mut tokens = get_allowed_tokens(&env);
+        if !tokens.contains(&token) {
+            tokens.push_back(token);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("tokens"), &tokens);
         }
 
-        // Verify milestone index is valid
-        if milestone_index >= escrow.milestones.len() {
-            return Err(Error::MilestoneNotFound);
-        }
+        Ok(())
+    }
 
-        // Get the milestone
-        let mut milestone = escrow
-            .milestones
-            .get(milestone_index)
-            .ok_or(Error::MilestoneNotFound)?;
+    /// Removes a token from the allowlist (admin only).
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    pub fn remove_allowed_token(env: Env, token: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
 
-        // CHECK IF ALREADY RELEASED FIRST - BEFORE ANY TOKEN OPERATIONS
-        if milestone.status == MilestoneStatus::Released {
-            return Err(Error::MilestoneAlreadyReleased);
+        require_admin(&env)?;
+
+        let mut tokens = get_allowed_tokens(&env);
+        if let Some(index) = tokens.first_index_of(&token) {
+            tokens.remove(index);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("tokens"), &tokens);
         }
 
-        // Get treasury and fee configuration
-        let (treasury, fee_bps) = Self::get_config(env.clone())?;
+        Ok(())
+    }
 
-        // Calculate platform fee using integer math
-        // fee = (amount * fee_bps) / 10000
-        let fee = calculate_fee(milestone.amount, fee_bps)?;
-        let payout = milestone
-            .amount
-            .checked_sub(fee)
-            .ok_or(Error::InvalidMilestoneAmount)?;
+    /// Returns the token allowlist; empty means all tokens are accepted.
+    pub fn allowed_tokens(env: Env) -> Vec<Address> {
+        get_allowed_tokens(&env)
+    }
 
-        // Create token client for transfers
-        let token_client = token::Client::new(&env, &token_address);
+    /// Adds an account to the creator allowlist (admin only).
+    /// While the allowlist is empty, anyone may create escrows.
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    pub fn add_allowed_creator(env: Env, creator: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
 
-        // Transfer payout to recipient (seller)
-        token_client.transfer(&env.current_contract_address(), &escrow.recipient, &payout);
+        require_admin(&env)?;
 
-        // Transfer fee to treasury (only if fee > 0)
-        if fee > 0 {
-            token_client.transfer(&env.current_contract_address(), &treasury, &fee);
+        let mut creators = get_allowed_creators(&env);
+        if !creators.contains(&creator) {
+            creators.push_back(creator);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("creators"), &creators);
+        }
 
-            // Emit event for fee collection
-            #[allow(deprecated)]
-            env.events().publish(
-                (symbol_short!("fee_coll"), escrow_id, milestone_index),
-                (fee, treasury.clone()),
-            );
+        Ok(())
+    }
+
+    /// Removes an account from the creator allowlist (admin only).
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    pub fn remove_allowed_creator(env: Env, creator: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        require_admin(&env)?;
+
+        let mut creators = get_allowed_creators(&env);
+        if let Some(index) = creators.first_index_of(&creator) {
+            creators.remove(index);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("creators"), &creators);
         }
 
-        // Update milestone status
-        milestone.status = MilestoneStatus::Released;
-        escrow.milestones.set(milestone_index, milestone.clone());
+        Ok(())
+    }
 
-        // Update total released with overflow protection
-        escrow.total_released = escrow
-            .total_released
-            .checked_add(milestone.amount)
-            .ok_or(Error::InvalidMilestoneAmount)?;
+    /// Returns the creator allowlist; empty means anyone may create escrows.
+    pub fn allowed_creators(env: Env) -> Vec<Address> {
+        get_allowed_creators(&env)
+    }
 
-        // Save updated escrow
-        env.storage().persistent().set(&storage_key, &escrow);
+    /// Returns the live contract configuration in a single read.
+    /// Before `initialize`, the admin and fee collector are unset and defaults apply.
+    pub fn config(env: Env) -> ContractConfig {
+        let storage = env.storage().instance();
 
-        // Extend TTL
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+        ContractConfig {
+            admin: get_admin(&env),
+            paused: is_paused(&env),
+            max_milestones: get_max_milestones(&env),
+            fee_bps: storage
+                .get(&symbol_short!("fee_bps"))
+                .unwrap_or(DEFAULT_FEE_BPS),
+            fee_collector: storage.get(&symbol_short!("treasury")),
+            dispute_fee: get_dispute_fee(&env),
+            max_escrow_amount: get_max_escrow_amount(&env),
+            max_tvl: get_max_tvl(&env),
+            min_milestone_amount: get_min_milestone_amount(&env),
+        }
+    }
 
-        // Emit event for milestone release
-        #[allow(deprecated)]
-        env.events().publish(
-            (symbol_short!("released"), escrow_id, milestone_index),
-            (payout, escrow.recipient.clone()),
-        );
+    /// Reports which optional subsystems this deployment has enabled, so
+    /// clients can adapt without reading each setting. Arbiters and vesting
+    /// schedules are always available and so have no flag.
+    pub fn features(env: Env) -> FeatureFlags {
+        let config = Self::config(env.clone());
 
-        Ok(())
+        FeatureFlags {
+            fees: config.fee_collector.is_some() && config.fee_bps > 0,
+            dispute_fees: config.dispute_fee > 0,
+            creator_allowlist: !get_allowed_creators(&env).is_empty(),
+            token_allowlist: !get_allowed_tokens(&env).is_empty(),
+            amount_cap: config.max_escrow_amount > 0,
+            tvl_cap: config.max_tvl > 0,
+            min_milestone: config.min_milestone_amount > 0,
+        }
+    }
+
+    /// Returns the current treasury address and fee configuration.
+    pub fn get_config(env: Env) -> Result<(Address, i128), Error> {
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("treasury"))
+            .ok_or(Error::TreasuryNotInitialized)?;
+
+        let fee_bps: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("fee_bps"))
+            .unwrap_or(DEFAULT_FEE_BPS);
+
+        Ok((treasury, fee_bps))
     }
 
-    /// Buyer confirms delivery and releases a milestone to the recipient.
+    /// Creates a new escrow with milestone-based payment releases.
+    /// NOTE: This only creates the escrow structure. Funds must be deposited separately via deposit_funds().
     ///
     /// # Arguments
-    /// * `escrow_id` - Identifier of the escrow
-    /// * `milestone_index` - Index of the milestone to release
-    /// * `buyer` - Buyer address confirming the delivery
+    /// * `escrow_id` - Unique identifier for the escrow
+    /// * `depositor` - Address funding the escrow
+    /// * `recipient` - Address receiving milestone payments
+    /// * `token_address` - Address of the token contract (e.g., XLM, USDC)
+    /// * `milestones` - Vector of milestones defining payment schedule
+    /// * `deadline` - Unix timestamp deadline for escrow completion
+    /// * `token` - Token contract address for payments
     ///
     /// # Errors
-    /// * `EscrowNotFound` - If escrow doesn't exist
-    /// * `UnauthorizedAccess` - If caller is not the buyer/depositor
-    /// * `EscrowNotActive` - If escrow is completed or cancelled
-    /// * `MilestoneNotFound` - If index is out of bounds
-    /// * `MilestoneAlreadyReleased` - If milestone was already released
-    pub fn confirm_delivery(
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowAlreadyExists` - If escrow_id is already in use
+    /// * `VectorTooLarge` - If more milestones than the configured maximum (default 20) are provided
+    /// * `InvalidMilestoneAmount` - If any milestone amount is zero or negative
+    /// * `MilestoneTooSmall` - If a milestone is below the configured minimum
+    /// * `InvalidMetadataUri` - If a milestone's metadata URI is empty or longer than 256 bytes
+    /// * `InvalidDependency` - If a milestone depends on itself or a later milestone
+    /// * `SelfDealing` - If the depositor is the recipient or a milestone's recipient
+    /// * `CreatorNotAllowed` - If a creator allowlist is configured and excludes the creator
+    /// * `TokenNotAllowed` - If a token allowlist is configured and excludes the token
+    /// * `AmountExceedsLimit` - If the total exceeds the configured per-escrow cap
+    pub fn create_escrow(
         env: Env,
         escrow_id: u64,
-        milestone_index: u32,
-        buyer: Address,
+        depositor: Address,
+        recipient: Address,
+        token_address: Address,
+        milestones: Vec<Milestone>,
+        deadline: u64,
     ) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
+        ensure_not_paused(&env)?;
+        ensure_unlocked(&env)?;
 
-        // Load escrow from storage
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        // Authenticate the depositor
+        depositor.require_auth();
+        ensure_creator_allowed(&env, &depositor)?;
 
-        // Security Check: Verify buyer authorization
-        buyer.require_auth();
+        let escrow = store_new_escrow(
+            &env,
+            escrow_id,
+            depositor,
+            recipient,
+            token_address,
+            milestones,
+            deadline,
+        )?;
 
-        // Security Check: Ensure caller is the depositor
-        if escrow.depositor != buyer {
-            return Err(Error::UnauthorizedAccess);
-        }
+        // The depositor signed, so the escrow can be listed under them now
+        add_to_depositor_index(&env, &escrow.depositor, escrow_id);
 
-        // Check escrow is active
-        if escrow.status != EscrowStatus::Active {
-            return Err(Error::EscrowNotActive);
-        }
+        // Emit event for escrow creation
+        publish_event(
+            &env,
+            symbol_short!("created"),
+            escrow_id,
+            (escrow.depositor, escrow.recipient, escrow.total_amount),
+        );
 
-        // Verify milestone index is valid
-        if milestone_index >= escrow.milestones.len() {
-            return Err(Error::MilestoneNotFound);
-        }
+        Ok(())
+    }
+
+    /// Recipient proposes an escrow for the depositor to fund, as with an
+    /// invoice. The escrow stays in Created state until the depositor funds
+    /// it through `accept_request`.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Unique identifier for the escrow
+    /// * `recipient` - Address proposing the escrow and receiving payments
+    /// * `depositor` - Address asked to fund the escrow
+    /// * `token_address` - Address of the token contract
+    /// * `milestones` - Vector of milestones defining payment schedule
+    /// * `deadline` - Unix timestamp deadline for escrow completion
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// Same as `create_escrow`, with the recipient authorizing instead of the
+    /// depositor and the creator allowlist applied to the recipient. Also:
+    /// * `TooManyOpenRequests` - If the recipient already has the maximum
+    ///   number of unfunded requests open
+    pub fn request_escrow(
+        env: Env,
+        escrow_id: u64,
+        recipient: Address,
+        depositor: Address,
+        token_address: Address,
+        milestones: Vec<Milestone>,
+        deadline: u64,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        ensure_unlocked(&env)?;
 
-        // Get the milestone
-        let mut milestone = escrow
-            .milestones
-            .get(milestone_index)
-            .ok_or(Error::MilestoneNotFound)?;
+        // Authenticate the recipient
+        recipient.require_auth();
+        ensure_creator_allowed(&env, &recipient)?;
 
-        // Check if already released
-        if milestone.status == MilestoneStatus::Released {
-            return Err(Error::MilestoneAlreadyReleased);
+        // Bound how many unfunded requests a single recipient can leave open
+        let open_key = get_open_requests_key(&recipient);
+        let open_requests: u32 = env.storage().persistent().get(&open_key).unwrap_or(0);
+        if open_requests >= MAX_OPEN_REQUESTS {
+            return Err(Error::TooManyOpenRequests);
         }
 
-        // Update milestone status
-        milestone.status = MilestoneStatus::Released;
-        escrow.milestones.set(milestone_index, milestone.clone());
+        let escrow = store_new_escrow(
+            &env,
+            escrow_id,
+            depositor,
+            recipient,
+            token_address,
+            milestones,
+            deadline,
+        )?;
 
-        // Update total released with overflow protection
-        escrow.total_released = escrow
-            .total_released
-            .checked_add(milestone.amount)
-            .ok_or(Error::InvalidMilestoneAmount)?;
+        // Track the request until it is funded or closed
+        let request_key = get_request_key(escrow_id);
+        env.storage()
+            .persistent()
+            .set(&request_key, &escrow.recipient);
+        env.storage()
+            .persistent()
+            .extend_ttl(&request_key, 100, 2_000_000);
+        env.storage()
+            .persistent()
+            .set(&open_key, &(open_requests + 1));
+        env.storage()
+            .persistent()
+            .extend_ttl(&open_key, 100, 2_000_000);
 
-        // Execute token transfer from contract to recipient
-        let token_client = token::Client::new(&env, &escrow.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &escrow.recipient,
-            &milestone.amount,
+        publish_event(
+            &env,
+            symbol_short!("requested"),
+            escrow_id,
+            (escrow.depositor, escrow.recipient, escrow.total_amount),
         );
 
-        // Save updated escrow
-        env.storage().persistent().set(&storage_key, &escrow);
-
         Ok(())
     }
 
-    /// Cancels an escrow before any milestones are released.
-    /// Returns all funds to the depositor.
+    /// Depositor accepts an escrow request by funding it. The depositor must
+    /// have approved this contract to spend the required amount.
     ///
     /// # Arguments
-    /// * `escrow_id` - Identifier of the escrow
+    /// * `escrow_id` - Identifier of the requested escrow
+    /// * `depositor` - Address the request was addressed to
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If `depositor` is not the escrow's depositor
+    /// * Any error of `deposit_funds`
+    pub fn accept_request(env: Env, escrow_id: u64, depositor: Address) -> Result<(), Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        if escrow.depositor != depositor {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        Self::deposit_funds(env, escrow_id)
+    }
+
+    /// Deposits funds into an escrow, transitioning it from Created to Active.
+    /// The depositor must have approved this contract to spend the required amount.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow to fund
     ///
     /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
     /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
     /// * `UnauthorizedAccess` - If caller is not the depositor
-    /// * `MilestoneAlreadyReleased` - If any milestone has been released
-    pub fn cancel_escrow(env: Env, escrow_id: u64) -> Result<(), Error> {
+    /// * `EscrowAlreadyFunded` - If escrow is already in Active state
+    /// * `FundingDeadlinePassed` - If the escrow's funding deadline has passed
+    /// * `TvlCapExceeded` - If the deposit would push total value locked past the cap
+    /// * `TokenTransferFailed` - If token transfer fails
+    pub fn deposit_funds(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
         let storage_key = get_storage_key(escrow_id);
 
+        // Load escrow from storage
         let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&storage_key)
             .ok_or(Error::EscrowNotFound)?;
 
-        // Verify authorization
+        // Verify authorization - only depositor can fund
         escrow.depositor.require_auth();
 
-        // Verify no milestones have been released
-        if escrow.total_released > 0 {
-            return Err(Error::MilestoneAlreadyReleased);
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Check escrow hasn't already been funded
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
         }
 
-        // If escrow was funded (Active status), refund the depositor
-        if escrow.status == EscrowStatus::Active {
-            let token_client = token::Client::new(&env, &escrow.token_address);
+        // Late deposits are refused so the escrow can be expired instead
+        if let Some(funding_deadline) = escrow.funding_deadline
+            && env.ledger().timestamp() > funding_deadline
+        {
+            return Err(Error::FundingDeadlinePassed);
+        }
 
-            // Transfer all funds back to depositor
-            token_client.transfer(
-                &env.current_contract_address(), // from (contract)
-                &escrow.depositor,               // to (depositor)
-                &escrow.total_amount,            // full amount
-            );
+        // Enforce the operator's contract-wide exposure cap on locked funds
+        let tvl = get_tvl(&env)
+            .checked_add(escrow.total_amount)
+            .ok_or(Error::TvlCapExceeded)?;
+        let max_tvl = get_max_tvl(&env);
+        if max_tvl > 0 && tvl > max_tvl {
+            return Err(Error::TvlCapExceeded);
         }
 
-        // Update status
-        escrow.status = EscrowStatus::Cancelled;
+        // Initialize token client for the specified token
+        let token_client = token::Client::new(&env, &escrow.token_address);
+
+        // Transfer tokens from depositor to contract
+        // NOTE: Depositor must have approved this contract to spend their tokens
+        safe_transfer_from(
+            &token_client,
+            &env.current_contract_address(), // spender (this contract)
+            &escrow.depositor,               // from (depositor's address)
+            &env.current_contract_address(), // to (contract's address - holds in escrow)
+            &escrow.total_amount,            // amount to transfer
+        )?;
+
+        // Update escrow status to Active
+        escrow.status = EscrowStatus::Active;
+        add_tvl(&env, escrow.total_amount);
+
+        // A request is only listed under its depositor once they fund it
+        if env.storage().persistent().has(&get_request_key(escrow_id)) {
+            add_to_depositor_index(&env, &escrow.depositor, escrow_id);
+        }
+        close_request(&env, escrow_id);
+
+        // Save updated escrow
         env.storage().persistent().set(&storage_key, &escrow);
 
         // Extend TTL
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+        extend_escrow_ttl(&env, escrow_id);
+
+        // Emit event for deposit
+        publish_event(
+            &env,
+            symbol_short!("funded"),
+            escrow_id,
+            escrow.total_amount,
+        );
+
+        exit(&env);
 
         Ok(())
     }
 
-    /// Marks an escrow as completed after all milestones are released.
+    /// Cancels an escrow that was not funded by its funding deadline.
+    /// Anyone may call this once the deadline has passed; a posted bond goes
+    /// back to the recipient.
     ///
     /// # Arguments
-    /// * `escrow_id` - Identifier of the escrow
+    /// * `escrow_id` - Identifier of the escrow to expire
     ///
     /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
     /// * `EscrowNotFound` - If escrow doesn't exist
-    /// * `UnauthorizedAccess` - If caller is not the depositor
-    /// * `EscrowNotActive` - If not all milestones are released
-    pub fn complete_escrow(env: Env, escrow_id: u64) -> Result<(), Error> {
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    /// * `ActionTooSoon` - If there is no funding deadline or it has not passed
+    pub fn expire_funding(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
         let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
 
-        let mut escrow: Escrow = env
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        let funding_deadline = match escrow.funding_deadline {
+            Some(funding_deadline) if env.ledger().timestamp() > funding_deadline => {
+                funding_deadline
+            }
+            _ => return Err(Error::ActionTooSoon),
+        };
+
+        // The recipient did nothing wrong, so a posted bond goes back to them
+        return_bond(&env, &mut escrow)?;
+
+        escrow.status = EscrowStatus::Cancelled;
+        close_request(&env, escrow_id);
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(&env, symbol_short!("expired"), escrow_id, funding_deadline);
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Lets the recipient reject an escrow before it is funded, cancelling it.
+    /// A bond the recipient posted is handed back.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow to decline
+    /// * `recipient` - Recipient of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If caller is not the escrow's recipient
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn decline_escrow(env: Env, escrow_id: u64, recipient: Address) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        recipient.require_auth();
+        if escrow.recipient != recipient {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        return_bond(&env, &mut escrow)?;
+
+        escrow.status = EscrowStatus::Cancelled;
+        close_request(&env, escrow_id);
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(&env, symbol_short!("declined"), escrow_id, recipient);
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Retrieves escrow details (read-only).
+    /// Private escrows must be read through `get_escrow_as` instead.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Result<Escrow, Error> {
+        load_public_escrow(&env, escrow_id)
+    }
+
+    /// Retrieves several escrows in one call, in the order requested.
+    /// Missing and private escrows come back as `None`.
+    ///
+    /// # Errors
+    /// * `VectorTooLarge` - If more than 50 ids are requested
+    pub fn get_escrows(env: Env, ids: Vec<u64>) -> Result<Vec<Option<Escrow>>, Error> {
+        if ids.len() > MAX_PAGE_SIZE {
+            return Err(Error::VectorTooLarge);
+        }
+
+        let mut escrows = Vec::new(&env);
+        for escrow_id in ids.iter() {
+            let escrow = load_escrow(&env, escrow_id)
+                .ok()
+                .filter(|escrow| !escrow.private);
+            escrows.push_back(escrow);
+        }
+        Ok(escrows)
+    }
+
+    /// Returns the status and released-milestone count of several escrows in
+    /// one call, as `(escrow_id, status, released)`. Missing and private
+    /// escrows are skipped.
+    ///
+    /// # Errors
+    /// * `VectorTooLarge` - If more than 50 ids are requested
+    pub fn statuses_of(env: Env, ids: Vec<u64>) -> Result<Vec<(u64, EscrowStatus, u32)>, Error> {
+        if ids.len() > MAX_PAGE_SIZE {
+            return Err(Error::VectorTooLarge);
+        }
+
+        let mut statuses = Vec::new(&env);
+        for escrow_id in ids.iter() {
+            if let Ok(escrow) = load_escrow(&env, escrow_id)
+                && !escrow.private
+            {
+                let released = escrow
+                    .milestones
+                    .iter()
+                    .filter(|milestone| milestone.status == MilestoneStatus::Released)
+                    .count() as u32;
+                statuses.push_back((escrow_id, escrow.status, released));
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Retrieves escrow details on behalf of a participant, including private escrows.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `caller` - Depositor, recipient or arbiter reading the escrow
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If caller is not a participant of a private escrow
+    pub fn get_escrow_as(env: Env, escrow_id: u64, caller: Address) -> Result<Escrow, Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        if escrow.private {
+            caller.require_auth();
+            if !is_participant(&escrow, &caller) {
+                return Err(Error::UnauthorizedAccess);
+            }
+        }
+        Ok(escrow)
+    }
+
+    /// Marks an escrow as private so `get_escrow` is restricted to participants.
+    /// This gates the contract's read functions only; ledger entries stay publicly observable.
+    ///
+    /// Every per-escrow read then fails with `UnauthorizedAccess` or reports
+    /// nothing, and participants read through `get_escrow_as`. Deliberately
+    /// left public: the id listings (`escrows_by_category`, `escrows_by_token`,
+    /// `escrows_of_depositor`), and `verify_invariants` and
+    /// `storage_footprint`, which expose only a health flag and a size.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `private` - Whether reads require participant authorization
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    pub fn set_private(env: Env, escrow_id: u64, private: bool) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        escrow.private = private;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Moves the escrow into a category for filtered listings.
+    /// Must be called by the depositor before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `category` - Category symbol, e.g. `freelance` or `rental`
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_category(env: Env, escrow_id: u64, category: Symbol) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        if escrow.category == category {
+            return Ok(());
+        }
+
+        remove_from_category(&env, &escrow.category, escrow_id);
+        add_to_category(&env, &category, escrow_id);
+
+        escrow.category = category;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Lists escrow ids in a category in creation order.
+    /// At most 50 index slots are read per call. Escrows moved to another
+    /// category leave their slot empty, so a page may hold fewer ids.
+    ///
+    /// # Arguments
+    /// * `category` - Category to filter by
+    /// * `start` - Offset of the first slot to read
+    /// * `limit` - Maximum number of slots to read
+    pub fn escrows_by_category(env: Env, category: Symbol, start: u32, limit: u32) -> Vec<u64> {
+        paginate(&env, &get_category_key(&category), start, limit)
+    }
+
+    /// Lists ids of escrows using a token, in creation order.
+    /// At most 50 ids are returned per call.
+    ///
+    /// # Arguments
+    /// * `token` - Token contract address to filter by
+    /// * `start` - Offset of the first id to return
+    /// * `limit` - Maximum number of ids to return
+    pub fn escrows_by_token(env: Env, token: Address, start: u32, limit: u32) -> Vec<u64> {
+        paginate(&env, &get_token_index_key(&token), start, limit)
+    }
+
+    /// Lists ids of escrows funded by a depositor, in the order they were
+    /// listed. Escrows the depositor created appear immediately; escrows a
+    /// recipient requested appear only once the depositor funds them.
+    /// At most 50 ids are returned per call.
+    ///
+    /// # Arguments
+    /// * `depositor` - Depositor address to filter by
+    /// * `start` - Offset of the first id to return
+    /// * `limit` - Maximum number of ids to return
+    pub fn escrows_of_depositor(env: Env, depositor: Address, start: u32, limit: u32) -> Vec<u64> {
+        paginate(&env, &get_depositor_index_key(&depositor), start, limit)
+    }
+
+    /// Extends the escrow's storage TTL so it is not archived. Anyone may
+    /// pay to keep an escrow alive.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    pub fn bump_escrow(env: Env, escrow_id: u64) -> Result<(), Error> {
+        load_escrow(&env, escrow_id)?;
+        extend_escrow_ttl(&env, escrow_id);
+        Ok(())
+    }
+
+    /// Read-only helper to fetch escrow status
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn get_state(env: Env, escrow_id: u64) -> Result<EscrowStatus, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        Ok(escrow.status)
+    }
+
+    /// Bundles everything known about an escrow into one snapshot, so an
+    /// indexer can sync its complete state in a single call.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn export_escrow(env: Env, escrow_id: u64) -> Result<EscrowExport, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+
+        let mut milestone_statuses = Vec::new(&env);
+        let mut disputes = Vec::new(&env);
+        for (index, milestone) in escrow.milestones.iter().enumerate() {
+            milestone_statuses.push_back(milestone.status);
+            let dispute_key = get_dispute_key(escrow_id, index as u32);
+            if let Some(dispute) = env.storage().persistent().get(&dispute_key) {
+                disputes.push_back((index as u32, dispute));
+            }
+        }
+
+        // Escrows cancelled before funding never moved any money; a funded
+        // escrow that was cancelled has its refund booked in total_refunded
+        let funded = matches!(
+            escrow.status,
+            EscrowStatus::Active | EscrowStatus::Completed
+        ) || escrow.total_released > 0
+            || escrow.total_refunded > 0;
+
+        Ok(EscrowExport {
+            escrow_id,
+            balance: escrow_balance(&escrow, &escrow.token_address),
+            milestone_statuses,
+            funded,
+            disputes,
+            exported_at: env.ledger().timestamp(),
+            escrow,
+        })
+    }
+
+    /// Returns the seconds elapsed since the escrow was created.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn escrow_age(env: Env, escrow_id: u64) -> Result<u64, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        Ok(env.ledger().timestamp().saturating_sub(escrow.created_at))
+    }
+
+    /// Returns the share of the escrow released to the recipient, as a whole
+    /// percentage rounded down.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn completion_percentage(env: Env, escrow_id: u64) -> Result<u32, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        if escrow.total_amount <= 0 {
+            return Ok(0);
+        }
+        let percent = escrow
+            .total_released
+            .checked_mul(100)
+            .ok_or(Error::InvalidMilestoneAmount)?
+            / escrow.total_amount;
+        Ok(percent as u32)
+    }
+
+    /// Estimates when the escrow will complete: the latest deadline among
+    /// milestones not yet paid out, or `None` if none of them has a deadline.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn estimated_completion(env: Env, escrow_id: u64) -> Result<Option<u64>, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+
+        let mut latest: Option<u64> = None;
+        for milestone in escrow.milestones.iter() {
+            if is_settled(&milestone) {
+                continue;
+            }
+            if let Some(deadline) = milestone.deadline {
+                latest = Some(latest.map_or(deadline, |current| current.max(deadline)));
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Returns the number of milestones in the escrow.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn milestone_count(env: Env, escrow_id: u64) -> Result<u32, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        Ok(escrow.milestones.len())
+    }
+
+    /// Returns the milestones still to be paid out, each with its index.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn remaining_milestones(env: Env, escrow_id: u64) -> Result<Vec<(u32, Milestone)>, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+
+        let mut remaining = Vec::new(&env);
+        for (index, milestone) in escrow.milestones.iter().enumerate() {
+            if !is_settled(&milestone) {
+                remaining.push_back((index as u32, milestone));
+            }
+        }
+        Ok(remaining)
+    }
+
+    /// Returns a single milestone, including its metadata URI.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    /// * `MilestoneNotFound` - If index is out of bounds
+    pub fn get_milestone(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<Milestone, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        milestone_at(&escrow, milestone_index)
+    }
+
+    /// Returns a single milestone's description.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    /// * `MilestoneNotFound` - If index is out of bounds
+    pub fn milestone_description(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<Symbol, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        let milestone = milestone_at(&escrow, milestone_index)?;
+        Ok(milestone.description)
+    }
+
+    /// Returns the role `address` plays in the escrow, so frontends can offer
+    /// the right actions for the connected wallet.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn role_of(env: Env, escrow_id: u64, address: Address) -> Result<EscrowRole, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        Ok(find_party(&escrow, &address).map_or(EscrowRole::None, |(_, role)| role))
+    }
+
+    /// Checks the escrow's bookkeeping for internal consistency, so monitoring
+    /// can catch storage corruption or migration bugs. Read-only.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    pub fn verify_invariants(env: Env, escrow_id: u64) -> Result<bool, Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        Ok(invariants_hold(&escrow))
+    }
+
+    /// Recomputes `total_released` from the milestones marked Released and
+    /// rewrites it, recovering from bookkeeping drift (admin only). Works on
+    /// frozen escrows, since those are the ones most likely to need it.
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `VestingEscrow` - If the escrow vests, as its milestones don't track partial claims
+    /// * `TotalAmountOverflow` - If the released milestones overflow
+    pub fn reconcile(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        require_admin(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_vesting(&escrow)?;
+
+        let mut released: i128 = 0;
+        for milestone in escrow.milestones.iter() {
+            if milestone.status == MilestoneStatus::Released {
+                released = released
+                    .checked_add(milestone.amount)
+                    .ok_or(Error::TotalAmountOverflow)?;
+            }
+        }
+
+        let before = escrow.total_released;
+        escrow.total_released = released;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("reconcile"),
+            escrow_id,
+            (before, released),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the position of `address` in `party_addresses`, or `None` if
+    /// it is not a party to the escrow.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn party_index(env: Env, escrow_id: u64, address: Address) -> Result<Option<u32>, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        Ok(find_party(&escrow, &address).map(|(index, _)| index))
+    }
+
+    /// Returns the escrow's party addresses: depositor, recipient, then the
+    /// arbiter if one is assigned.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn party_addresses(env: Env, escrow_id: u64) -> Result<Vec<Address>, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+
+        let mut parties = Vec::new(&env);
+        parties.push_back(escrow.depositor);
+        parties.push_back(escrow.recipient);
+        if let Some(arbiter) = escrow.arbiter {
+            parties.push_back(arbiter);
+        }
+        Ok(parties)
+    }
+
+    /// Returns `(milestone_index, release_timestamp)` for every milestone
+    /// released to the recipient, in milestone order.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn release_history(env: Env, escrow_id: u64) -> Result<Vec<(u32, u64)>, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+
+        let mut history = Vec::new(&env);
+        for (index, milestone) in escrow.milestones.iter().enumerate() {
+            if let Some(released_at) = milestone.released_at {
+                history.push_back((index as u32, released_at));
+            }
+        }
+        Ok(history)
+    }
+
+    /// Returns the amount `depositor` would get back by cancelling the escrow
+    /// now: the remaining balance of a funded escrow with no releases, else 0.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn refundable_amount(env: Env, escrow_id: u64, depositor: Address) -> Result<i128, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+
+        if escrow.depositor != depositor || escrow.total_released > 0 {
+            return Ok(0);
+        }
+        Ok(escrow_balance(&escrow, &escrow.token_address))
+    }
+
+    /// Returns the approximate stored size of the escrow in bytes, measured
+    /// as the length of its XDR encoding. Useful for estimating rent.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    pub fn storage_footprint(env: Env, escrow_id: u64) -> Result<u32, Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        Ok(escrow.to_xdr(&env).len())
+    }
+
+    /// Appends a note to the escrow's communication trail.
+    /// An escrow holds at most 20 notes.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `author` - Depositor, recipient or arbiter writing the note
+    /// * `note` - Note text (max 256 bytes)
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If author is not a participant
+    /// * `InvalidNote` - If note is empty or longer than 256 bytes
+    /// * `TooManyNotes` - If the escrow already holds the maximum number of notes
+    pub fn add_note(env: Env, escrow_id: u64, author: Address, note: String) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify author authorization
+        author.require_auth();
+
+        if !is_participant(&escrow, &author) {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        if note.is_empty() || note.len() > MAX_NOTE_LEN {
+            return Err(Error::InvalidNote);
+        }
+
+        let notes_key = get_notes_key(escrow_id);
+        let mut notes: Vec<(Address, u64, String)> = env
+            .storage()
+            .persistent()
+            .get(&notes_key)
+            .unwrap_or(Vec::new(&env));
+        if notes.len() >= MAX_NOTES {
+            return Err(Error::TooManyNotes);
+        }
+        notes.push_back((author, env.ledger().timestamp(), note));
+        env.storage().persistent().set(&notes_key, &notes);
+
+        // Extend TTL, keeping the escrow alive while it is in use
+        env.storage()
+            .persistent()
+            .extend_ttl(&notes_key, 100, 2_000_000);
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Returns the escrow's notes as `(author, timestamp, note)`, oldest first.
+    /// Private escrows report no notes.
+    pub fn get_notes(env: Env, escrow_id: u64) -> Vec<(Address, u64, String)> {
+        if is_private(&env, escrow_id) {
+            return Vec::new(&env);
+        }
+        env.storage()
+            .persistent()
+            .get(&get_notes_key(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the escrow's most recent lifecycle events, oldest first.
+    /// Only the last 16 events are retained. Private escrows report none.
+    pub fn recent_events(env: Env, escrow_id: u64) -> Vec<EscrowEventRecord> {
+        if is_private(&env, escrow_id) {
+            return Vec::new(&env);
+        }
+        env.storage()
+            .persistent()
+            .get(&get_events_key(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Releases a specific milestone payment to the recipient with platform fee deduction.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone to release
+    /// * `token_address` - Address of the token contract for transfers
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `UnauthorizedAccess` - If caller is not the depositor
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `VestingEscrow` - If the escrow pays out through a vesting schedule
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `MilestoneAlreadyReleased` - If milestone was already released
+    /// * `MilestoneDisputed` - If milestone is under dispute
+    /// * `InsufficientBalance` - If the release exceeds this escrow's remaining balance
+    /// * `DependencyNotMet` - If the milestone's prerequisite has not been released
+    /// * `ConditionsNotMet` - If the escrow's verifier or price condition rejects the release
+    /// * `ActionTooSoon` - If the minimum action interval or release cooldown has not elapsed
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `RecipientNotWhitelisted` - If the payee is outside the escrow's recipient whitelist
+    ///
+    /// # Fee Calculation
+    /// Platform fee is calculated using basis points: fee = (amount * fee_bps) / 10000
+    /// The recipient receives: amount - fee
+    /// The treasury receives: fee
+    pub fn release_milestone(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        // Load escrow from storage
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+
+        // Verify authorization - only depositor can release funds
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // The depositor or admin may hold releases on this escrow
+        ensure_not_escrow_paused(&escrow)?;
+
+        // Check escrow is active (funds deposited)
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Vesting escrows pay out through claim_vested only
+        ensure_not_vesting(&escrow)?;
+
+        // Load the milestone, rejecting an out-of-range index
+        let milestone = milestone_at(&escrow, milestone_index)?;
+
+        // CHECK IF ALREADY RELEASED FIRST - BEFORE ANY TOKEN OPERATIONS
+        if is_settled(&milestone) {
+            return Err(Error::MilestoneAlreadyReleased);
+        }
+
+        // Disputed milestones are frozen until the dispute is resolved
+        if milestone.status == MilestoneStatus::Disputed {
+            return Err(Error::MilestoneDisputed);
+        }
+
+        // Get treasury and fee configuration
+        let (treasury, fee_bps) = Self::get_config(env.clone())?;
+
+        // Ensure this escrow alone holds enough of the token to cover the release
+        ensure_escrow_balance(&escrow, &token_address, milestone.amount)?;
+
+        // Prerequisite milestones must be released first
+        ensure_dependency_met(&escrow, &milestone)?;
+
+        // Defer to the escrow's verifier for any external release conditions
+        ensure_conditions_met(&env, &escrow, escrow_id)?;
+
+        // Throttle rapid state changes on contentious escrows
+        record_action(&env, &mut escrow)?;
+
+        // Space out releases so the recipient or arbiter can react
+        record_release(&env, &mut escrow)?;
+
+        // Pay the recipient (seller) and the platform fee to the treasury
+        let plan = pay_out(
+            &env,
+            &mut escrow,
+            escrow_id,
+            milestone_index,
+            &treasury,
+            fee_bps,
+            true,
+        )?;
+        let payout = plan.to_recipient;
+
+        // Save updated escrow
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        notify_release(&env, &escrow, escrow_id, milestone_index, payout);
+
+        // Emit event for milestone release
+        publish_event(
+            &env,
+            symbol_short!("released"),
+            escrow_id,
+            (milestone_index, payout, plan.recipient),
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Releases a milestone like `release_milestone`, but at most once per
+    /// external reference. Retrying with a reference already used for the
+    /// same milestone succeeds without moving funds again, so off-chain
+    /// triggers can be replayed safely.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone to release
+    /// * `token_address` - Address of the token contract for transfers
+    /// * `release_ref` - Caller-chosen reference identifying the release request
+    ///
+    /// # Errors
+    /// * `DuplicateReference` - If the reference was already used for a
+    ///   different milestone of this escrow
+    ///
+    /// Otherwise fails as `release_milestone` does the first time a
+    /// reference is used.
+    pub fn release_with_ref(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        token_address: Address,
+        release_ref: BytesN<32>,
+    ) -> Result<(), Error> {
+        let ref_key = get_release_ref_key(escrow_id, &release_ref);
+        if let Some(used_for) = env.storage().persistent().get::<_, u32>(&ref_key) {
+            if used_for != milestone_index {
+                return Err(Error::DuplicateReference);
+            }
+            return Ok(());
+        }
+
+        Self::release_milestone(env.clone(), escrow_id, milestone_index, token_address)?;
+
+        env.storage().persistent().set(&ref_key, &milestone_index);
+        env.storage()
+            .persistent()
+            .extend_ttl(&ref_key, 100, 2_000_000);
+
+        Ok(())
+    }
+
+    /// Releases every unsettled milestone to its payee and completes the
+    /// escrow, for when the depositor accepts the whole project at once.
+    /// Fees apply to each milestone as with `release_milestone`, and so does
+    /// the release cooldown: with one set, the call fails unless a single
+    /// milestone is left to release.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `UnauthorizedAccess` - If caller is not the depositor
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `VestingEscrow` - If the escrow pays out through a vesting schedule
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `ConditionsNotMet` - If the escrow's verifier or price condition rejects the release
+    /// * `ActionTooSoon` - If the minimum action interval or release cooldown has not elapsed
+    /// * `MilestoneDisputed` - If any unsettled milestone is under dispute
+    /// * `DependencyNotMet` - If a milestone depends on one that was refunded
+    /// * `InsufficientBalance` - If the releases exceed this escrow's remaining balance
+    /// * `RecipientNotWhitelisted` - If the payee is outside the escrow's recipient whitelist
+    pub fn release_all(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization - only depositor can release funds
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // The depositor or admin may hold releases on this escrow
+        ensure_not_escrow_paused(&escrow)?;
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Vesting escrows pay out through claim_vested only
+        ensure_not_vesting(&escrow)?;
+
+        let (treasury, fee_bps) = Self::get_config(env.clone())?;
+
+        // Defer to the escrow's verifier for any external release conditions
+        ensure_conditions_met(&env, &escrow, escrow_id)?;
+
+        // The whole batch counts as a single action
+        record_action(&env, &mut escrow)?;
+
+        // Release milestones in order, so dependencies on earlier milestones
+        // in the same batch are satisfied. Quiet escrows only announce the
+        // completion below.
+        let announce = !escrow.quiet;
+        let mut payouts: Vec<(u32, i128)> = Vec::new(&env);
+        for index in 0..escrow.milestones.len() {
+            let milestone = milestone_at(&escrow, index)?;
+
+            if is_settled(&milestone) {
+                continue;
+            }
+            if milestone.status == MilestoneStatus::Disputed {
+                return Err(Error::MilestoneDisputed);
+            }
+            ensure_dependency_met(&escrow, &milestone)?;
+
+            // Each milestone observes the release cooldown on its own
+            record_release(&env, &mut escrow)?;
+
+            let plan = pay_out(
+                &env,
+                &mut escrow,
+                escrow_id,
+                index,
+                &treasury,
+                fee_bps,
+                announce,
+            )?;
+            if announce {
+                publish_event(
+                    &env,
+                    symbol_short!("released"),
+                    escrow_id,
+                    (index, plan.to_recipient, plan.recipient),
+                );
+            }
+            payouts.push_back((index, plan.to_recipient));
+        }
+
+        // A completed escrow owes the recipient its bond back
+        return_bond(&env, &mut escrow)?;
+
+        // Update status
+        escrow.status = EscrowStatus::Completed;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        for (index, payout) in payouts.iter() {
+            notify_release(&env, &escrow, escrow_id, index, payout);
+        }
+
+        // Emit event for completion
+        publish_event(
+            &env,
+            symbol_short!("completed"),
+            escrow_id,
+            escrow.total_released,
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Previews how `release_milestone` would distribute a milestone without
+    /// moving funds or requiring authorization.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone to preview
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `RecipientNotWhitelisted` - If the payee is outside the escrow's recipient whitelist
+    pub fn simulate_release(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<ReleasePlan, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        let milestone = milestone_at(&escrow, milestone_index)?;
+
+        let (_, fee_bps) = Self::get_config(env)?;
+        plan_release(&escrow, &milestone, fee_bps)
+    }
+
+    /// Returns whether `release_milestone` would currently succeed for the
+    /// milestone, evaluating every gate short of the depositor's signature.
+    /// Always `false` for private escrows.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone to check
+    pub fn is_releasable(env: Env, escrow_id: u64, milestone_index: u32) -> bool {
+        check_releasable(&env, escrow_id, milestone_index).is_ok()
+    }
+
+    /// Returns roughly how many seconds remain until the milestone's time
+    /// locks lift: the vesting cliff, the release cooldown and the minimum
+    /// action interval. Ledger-based throttles are converted at about five
+    /// seconds per ledger. `Some(0)` means no lock applies now; `None` means
+    /// waiting alone will never make it releasable, for example while it is
+    /// disputed or once it has been settled.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    /// * `MilestoneNotFound` - If index is out of bounds
+    pub fn time_until_releasable(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<Option<u64>, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        let milestone = milestone_at(&escrow, milestone_index)?;
+
+        if escrow.status != EscrowStatus::Active
+            || is_settled(&milestone)
+            || milestone.status == MilestoneStatus::Disputed
+        {
+            return Ok(None);
+        }
+
+        // Vesting escrows start paying out at the cliff
+        if is_vesting(&escrow) {
+            let cliff_at = escrow.vesting.start.saturating_add(escrow.vesting.cliff);
+            return Ok(Some(cliff_at.saturating_sub(env.ledger().timestamp())));
+        }
+
+        let current = env.ledger().sequence();
+        let remaining = |last: Option<u32>, interval: u32| {
+            last.map_or(0, |last| {
+                last.saturating_add(interval).saturating_sub(current)
+            })
+        };
+        let ledgers = remaining(escrow.last_release_ledger, escrow.release_cooldown).max(
+            remaining(escrow.last_action_ledger, escrow.min_action_interval),
+        );
+        Ok(Some(ledgers as u64 * LEDGER_SECONDS))
+    }
+
+    /// Recipient configures the signers who must approve a delivery before
+    /// the buyer can confirm it. Must be called before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `signers` - Recipient-side signers, empty to remove the quorum
+    /// * `threshold` - Approvals required, 0 when `signers` is empty
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    /// * `InvalidThreshold` - If the threshold is zero or exceeds the signer count
+    pub fn set_recipient_signers(
+        env: Env,
+        escrow_id: u64,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify recipient authorization
+        escrow.recipient.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        let disabling = signers.is_empty() && threshold == 0;
+        if !disabling && (threshold == 0 || threshold > signers.len()) {
+            return Err(Error::InvalidThreshold);
+        }
+
+        escrow.recipient_signers = signers;
+        escrow.recipient_threshold = threshold;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Restricts payouts to the listed addresses, guarding against funds
+    /// going somewhere unexpected. Must be called by the depositor before
+    /// the escrow is funded; an empty list removes the restriction.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `whitelist` - Addresses that may receive milestone payouts
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_recipient_whitelist(
+        env: Env,
+        escrow_id: u64,
+        whitelist: Vec<Address>,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.recipient_whitelist = whitelist;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Requires the recipient to submit a delivery proof before the buyer can
+    /// confirm a milestone. Must be called by the depositor before the escrow
+    /// is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `require_proof` - Whether confirmations need a submitted proof
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_require_proof(env: Env, escrow_id: u64, require_proof: bool) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.require_proof = require_proof;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Lets a single-milestone escrow pay out without buyer confirmation:
+    /// once the recipient submits a delivery, anyone may call
+    /// `claim_auto_release` after a one-day challenge window unless the
+    /// depositor disputes first. Must be called by the depositor before the
+    /// escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `auto_release` - Whether an unchallenged delivery releases itself
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    /// * `AutoReleaseUnsupported` - If the escrow has more than one milestone
+    pub fn set_auto_release(env: Env, escrow_id: u64, auto_release: bool) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        if auto_release && escrow.milestones.len() != 1 {
+            return Err(Error::AutoReleaseUnsupported);
+        }
+
+        escrow.auto_release = auto_release;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Recipient records a hash of the delivered work for a milestone.
+    /// Submitting again replaces the previous proof.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the delivered milestone
+    /// * `proof_hash` - Hash of the delivery evidence
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `MilestoneAlreadyReleased` - If milestone was already released
+    pub fn submit_delivery(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        proof_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        ensure_unlocked(&env)?;
+
+        let escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify recipient authorization
+        escrow.recipient.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        let milestone = milestone_at(&escrow, milestone_index)?;
+        if is_settled(&milestone) {
+            return Err(Error::MilestoneAlreadyReleased);
+        }
+
+        let proof_key = get_proof_key(escrow_id, milestone_index);
+        env.storage().persistent().set(&proof_key, &proof_hash);
+
+        // Extend TTL, keeping the escrow alive while it is in use
+        env.storage()
+            .persistent()
+            .extend_ttl(&proof_key, 100, 2_000_000);
+
+        // Each submission restarts the challenge window of an auto-release
+        if escrow.auto_release {
+            let delivered_key = get_delivered_key(escrow_id, milestone_index);
+            env.storage()
+                .persistent()
+                .set(&delivered_key, &env.ledger().timestamp());
+            env.storage()
+                .persistent()
+                .extend_ttl(&delivered_key, 100, 2_000_000);
+        }
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("delivered"),
+            escrow_id,
+            (milestone_index, proof_hash),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the delivery proof submitted for a milestone, if any.
+    /// Private escrows report none.
+    pub fn delivery_proof(env: Env, escrow_id: u64, milestone_index: u32) -> Option<BytesN<32>> {
+        if is_private(&env, escrow_id) {
+            return None;
+        }
+        env.storage()
+            .persistent()
+            .get(&get_proof_key(escrow_id, milestone_index))
+    }
+
+    /// Recipient-side signer approves delivery of a milestone.
+    /// Approving twice has no further effect.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the delivered milestone
+    /// * `signer` - Recipient-side signer giving approval
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `UnauthorizedAccess` - If signer is not one of the recipient signers
+    /// * `EscrowNotActive` - If escrow is not active
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `MilestoneAlreadyReleased` - If milestone was already released
+    pub fn approve_delivery(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        signer: Address,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        ensure_unlocked(&env)?;
+
+        let escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify signer authorization
+        signer.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        if !escrow.recipient_signers.contains(&signer) {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        let milestone = milestone_at(&escrow, milestone_index)?;
+        if is_settled(&milestone) {
+            return Err(Error::MilestoneAlreadyReleased);
+        }
+
+        let approvals_key = get_approvals_key(escrow_id, milestone_index);
+        let mut approvals: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&approvals_key)
+            .unwrap_or(Vec::new(&env));
+        if approvals.contains(&signer) {
+            return Ok(());
+        }
+        approvals.push_back(signer.clone());
+        env.storage().persistent().set(&approvals_key, &approvals);
+
+        // Extend TTL, keeping the escrow alive while it is in use
+        env.storage()
+            .persistent()
+            .extend_ttl(&approvals_key, 100, 2_000_000);
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("approved"),
+            escrow_id,
+            (milestone_index, signer),
+        );
+
+        Ok(())
+    }
+
+    /// Buyer confirms delivery and releases a milestone to the recipient,
+    /// net of the platform fee as with `release_milestone`.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone to release
+    /// * `buyer` - Buyer address confirming the delivery
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `UnauthorizedAccess` - If caller is not the buyer/depositor
+    /// * `EscrowNotActive` - If escrow is completed or cancelled
+    /// * `VestingEscrow` - If the escrow pays out through a vesting schedule
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `MilestoneAlreadyReleased` - If milestone was already released
+    /// * `MilestoneDisputed` - If milestone is under dispute
+    /// * `InsufficientBalance` - If the release exceeds this escrow's remaining balance
+    /// * `DependencyNotMet` - If the milestone's prerequisite has not been released
+    /// * `ConditionsNotMet` - If the verifier or price condition rejects the release, or a required proof is missing
+    /// * `QuorumNotReached` - If too few recipient signers have approved the delivery
+    /// * `ActionTooSoon` - If the minimum action interval or release cooldown has not elapsed
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `RecipientNotWhitelisted` - If the payee is outside the escrow's recipient whitelist
+    pub fn confirm_delivery(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        buyer: Address,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        // Load escrow from storage
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+
+        // Security Check: Verify buyer authorization
+        buyer.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // The depositor or admin may hold releases on this escrow
+        ensure_not_escrow_paused(&escrow)?;
+
+        // Security Check: Ensure caller is the depositor
+        if escrow.depositor != buyer {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        pay_delivery(&env, &storage_key, &mut escrow, escrow_id, milestone_index)?;
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Releases an auto-releasing escrow's milestone once its delivery has
+    /// gone unchallenged for the challenge window. Callable by anyone; a
+    /// dispute raised within the window blocks it.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `AutoReleaseUnsupported` - If auto-release is not enabled on the escrow
+    /// * `ActionTooSoon` - If no delivery was submitted or the window has not elapsed
+    /// * `EscrowNotActive` - If escrow is completed or cancelled
+    /// * `MilestoneAlreadyReleased` - If the milestone was already released
+    /// * `MilestoneDisputed` - If the depositor disputed the delivery
+    ///
+    /// Other release checks fail as in `confirm_delivery`.
+    pub fn claim_auto_release(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // The depositor or admin may hold releases on this escrow
+        ensure_not_escrow_paused(&escrow)?;
+
+        if !escrow.auto_release {
+            return Err(Error::AutoReleaseUnsupported);
+        }
+
+        let delivered_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&get_delivered_key(escrow_id, 0))
+            .ok_or(Error::ActionTooSoon)?;
+        if env.ledger().timestamp() <= delivered_at.saturating_add(AUTO_RELEASE_WINDOW) {
+            return Err(Error::ActionTooSoon);
+        }
+
+        pay_delivery(&env, &storage_key, &mut escrow, escrow_id, 0)?;
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Recipient signals that work on a pending milestone has begun.
+    /// The milestone can still be released or disputed as before.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone being started
+    /// * `recipient` - Escrow recipient, or the milestone's own recipient
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `UnauthorizedAccess` - If caller is not the milestone's recipient
+    /// * `MilestoneAlreadyStarted` - If work on the milestone was already started
+    /// * `MilestoneAlreadyReleased` - If milestone was already released
+    /// * `MilestoneDisputed` - If milestone is under dispute
+    pub fn start_milestone(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        recipient: Address,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify recipient authorization
+        recipient.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Check escrow is active
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        let mut milestone = milestone_at(&escrow, milestone_index)?;
+
+        if recipient != escrow.recipient && milestone.recipient.as_ref() != Some(&recipient) {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        match milestone.status {
+            MilestoneStatus::Pending => {}
+            MilestoneStatus::InProgress => return Err(Error::MilestoneAlreadyStarted),
+            MilestoneStatus::Released | MilestoneStatus::Refunded => {
+                return Err(Error::MilestoneAlreadyReleased);
+            }
+            MilestoneStatus::Disputed => return Err(Error::MilestoneDisputed),
+        }
+
+        milestone.status = MilestoneStatus::InProgress;
+        escrow.milestones.set(milestone_index, milestone);
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("started"),
+            escrow_id,
+            (milestone_index, recipient),
+        );
+
+        Ok(())
+    }
+
+    /// Raises a dispute on a pending milestone, blocking its release.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone being disputed
+    /// * `disputer` - Depositor or recipient raising the dispute
+    /// * `reason` - Short explanation recorded for arbiters (max 64 bytes)
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `UnauthorizedAccess` - If disputer is neither depositor nor recipient
+    /// * `InvalidReason` - If reason is empty or longer than 64 bytes
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `MilestoneAlreadyReleased` - If milestone was already released
+    /// * `MilestoneDisputed` - If milestone is already under dispute
+    /// * `ActionTooSoon` - If the escrow's minimum action interval has not elapsed
+    pub fn dispute_milestone(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        disputer: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+
+        // Verify disputer authorization
+        disputer.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Only the escrow participants may raise a dispute
+        if disputer != escrow.depositor && disputer != escrow.recipient {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        if reason.is_empty() || reason.len() > MAX_REASON_LEN {
+            return Err(Error::InvalidReason);
+        }
+
+        // Check escrow is active
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Load the milestone, rejecting an out-of-range index
+        let mut milestone = milestone_at(&escrow, milestone_index)?;
+
+        match milestone.status {
+            MilestoneStatus::Pending | MilestoneStatus::InProgress => {}
+            MilestoneStatus::Released | MilestoneStatus::Refunded => {
+                return Err(Error::MilestoneAlreadyReleased);
+            }
+            MilestoneStatus::Disputed => return Err(Error::MilestoneDisputed),
+        }
+
+        // Throttle rapid state changes on contentious escrows
+        record_action(&env, &mut escrow)?;
+
+        // Collect the dispute fee, held until the dispute is resolved
+        let fee = get_dispute_fee(&env);
+        if fee > 0 {
+            let token_client = token::Client::new(&env, &escrow.token_address);
+            safe_transfer(
+                &token_client,
+                &disputer,
+                &env.current_contract_address(),
+                &fee,
+            )?;
+        }
+
+        // Preserve the pre-dispute state for the arbiter. A milestone keeps
+        // the snapshot of its first dispute; later disputes never replace it.
+        let snapshot_key = get_snapshot_key(escrow_id, milestone_index);
+        if !env.storage().persistent().has(&snapshot_key) {
+            let snapshot = DisputeSnapshot {
+                milestone_index,
+                taken_at: env.ledger().timestamp(),
+                milestones: escrow.milestones.clone(),
+            };
+            env.storage().persistent().set(&snapshot_key, &snapshot);
+            env.storage()
+                .persistent()
+                .extend_ttl(&snapshot_key, 100, 2_000_000);
+        }
+
+        // Update milestone status
+        milestone.status = MilestoneStatus::Disputed;
+        escrow.milestones.set(milestone_index, milestone);
+
+        // Record the dispute alongside the escrow
+        let dispute = DisputeInfo {
+            disputer: disputer.clone(),
+            reason: reason.clone(),
+            disputed_at: env.ledger().timestamp(),
+            resolved: false,
+            fee,
+            upheld: false,
+        };
+        let dispute_key = get_dispute_key(escrow_id, milestone_index);
+        env.storage().persistent().set(&dispute_key, &dispute);
+        env.storage()
+            .persistent()
+            .extend_ttl(&dispute_key, 100, 2_000_000);
+
+        // Save updated escrow
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        // Emit event for dispute
+        publish_event(
+            &env,
+            symbol_short!("disputed"),
+            escrow_id,
+            (milestone_index, disputer, reason),
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Assigns an arbiter who may resolve disputes on this escrow.
+    /// Must be called by the depositor before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `arbiter` - Address allowed to resolve disputes
+    /// * `arbiter_cap` - Maximum total amount the arbiter may redirect
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    /// * `InvalidArbiterCap` - If the cap is negative
+    pub fn set_arbiter(
+        env: Env,
+        escrow_id: u64,
+        arbiter: Address,
+        arbiter_cap: i128,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        if arbiter_cap < 0 {
+            return Err(Error::InvalidArbiterCap);
+        }
+
+        escrow.arbiter = Some(arbiter);
+        escrow.arbiter_cap = arbiter_cap;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Assigns a verifier contract whose `check(escrow_id)` must return true
+    /// before any milestone is released. Must be called by the depositor
+    /// before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `verifier` - Verifier contract address, or `None` to remove it
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_verifier(env: Env, escrow_id: u64, verifier: Option<Address>) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.verifier = verifier;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Makes releases contingent on an oracle price: every release checks
+    /// `oracle.price(asset)` against the threshold first. Must be called by
+    /// the depositor before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `price_condition` - Condition to enforce, or `None` to remove it
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_price_condition(
+        env: Env,
+        escrow_id: u64,
+        price_condition: Option<PriceCondition>,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        let condition_key = get_price_condition_key(escrow_id);
+        match price_condition {
+            Some(condition) => {
+                env.storage().persistent().set(&condition_key, &condition);
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&condition_key, 100, 2_000_000);
+            }
+            None => env.storage().persistent().remove(&condition_key),
+        }
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Registers a contract whose `on_release(escrow_id, milestone_index,
+    /// amount)` is called after each milestone release. Notification is best
+    /// effort: a failing callback never reverts the release. Must be called
+    /// by the depositor before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `callback` - Listener contract address, or `None` to remove it
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_callback(env: Env, escrow_id: u64, callback: Option<Address>) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.callback = callback;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Records the token's display symbol and decimals on the escrow, so
+    /// clients can format amounts without querying the token contract.
+    /// Must be called by the depositor before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `token_symbol` - Symbol shown next to amounts
+    /// * `token_decimals` - Number of decimal places the token uses
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_currency_metadata(
+        env: Env,
+        escrow_id: u64,
+        token_symbol: Symbol,
+        token_decimals: u32,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.token_symbol = Some(token_symbol);
+        escrow.token_decimals = Some(token_decimals);
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Sets how long a dispute may stay unresolved and which side it then
+    /// defaults to. Must be called by the depositor before the escrow is
+    /// funded; a zero window disables stale-dispute resolution.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `window` - Seconds after a dispute is raised before the default applies
+    /// * `default` - Side the milestone goes to once the window elapses
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_dispute_default(
+        env: Env,
+        escrow_id: u64,
+        window: u64,
+        default: DisputeDefault,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.dispute_window = window;
+        escrow.dispute_default = default;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Sets the minimum number of ledgers between successive releases and
+    /// disputes on this escrow. Must be called by the depositor before the
+    /// escrow is funded; zero disables the throttle.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `interval` - Minimum ledgers between state-changing actions
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_min_action_interval(env: Env, escrow_id: u64, interval: u32) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.min_action_interval = interval;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Turns quiet mode on or off for an escrow. Quiet escrows skip the
+    /// per-milestone events of batch operations such as `release_all`, while
+    /// creation, completion and single releases are still announced.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `quiet` - Whether to suppress non-essential events
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is completed or cancelled
+    pub fn set_quiet(env: Env, escrow_id: u64, quiet: bool) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Verbosity is not part of the terms, so it may change after funding
+        if escrow.status != EscrowStatus::Created && escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        escrow.quiet = quiet;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Sets the time by which the depositor must fund the escrow. After it
+    /// passes, deposits are refused and anyone may call `expire_funding`.
+    /// Must be called by the depositor before the escrow is funded; `None`
+    /// removes the deadline.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `funding_deadline` - Ledger timestamp the deposit must land by
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    /// * `InvalidDeadline` - If the deadline is not in the future
+    pub fn set_funding_deadline(
+        env: Env,
+        escrow_id: u64,
+        funding_deadline: Option<u64>,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        if let Some(funding_deadline) = funding_deadline
+            && funding_deadline <= env.ledger().timestamp()
+        {
+            return Err(Error::InvalidDeadline);
+        }
+
+        escrow.funding_deadline = funding_deadline;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Pushes back the escrow's completion deadline. Either participant may
+    /// extend it at any point before the escrow completes or is cancelled;
+    /// the deadline can only move later, never earlier.
+    ///
+    /// The escrow deadline is informational: no release, dispute or refund
+    /// path checks it, so extending it only updates the stored value that
+    /// clients read back through `get_escrow`.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `new_deadline` - New completion deadline (ledger timestamp)
+    /// * `caller` - Depositor or recipient requesting the extension
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If caller is not the depositor or recipient
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If the escrow is completed or cancelled
+    /// * `InvalidDeadline` - If the new deadline is not later than both the
+    ///   current deadline and the current time
+    pub fn extend_deadline(
+        env: Env,
+        escrow_id: u64,
+        new_deadline: u64,
+        caller: Address,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        caller.require_auth();
+
+        if caller != escrow.depositor && caller != escrow.recipient {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        if escrow.status == EscrowStatus::Completed || escrow.status == EscrowStatus::Cancelled {
+            return Err(Error::EscrowNotActive);
+        }
+
+        if new_deadline <= escrow.deadline || new_deadline <= env.ledger().timestamp() {
+            return Err(Error::InvalidDeadline);
+        }
+
+        let previous = escrow.deadline;
+        escrow.deadline = new_deadline;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("dl_ext"),
+            escrow_id,
+            (caller, previous, new_deadline),
+        );
+
+        Ok(())
+    }
+
+    /// Sets the minimum number of ledgers between consecutive milestone
+    /// releases on this escrow. Must be called by the depositor before the
+    /// escrow is funded; zero disables the cooldown.
+    ///
+    /// Every depositor-driven payout counts as a release: `release_milestone`,
+    /// `release_with_ref`, `release_all` and `settle_escrow` (one per
+    /// milestone), `confirm_delivery` and `claim_auto_release`. Dispute
+    /// resolutions are exempt, since the arbiter's ruling is the reaction the
+    /// cooldown leaves room for, and so are `claim_vested` payouts, which
+    /// follow their own schedule.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `cooldown` - Minimum ledgers between releases
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    pub fn set_release_cooldown(env: Env, escrow_id: u64, cooldown: u32) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.release_cooldown = cooldown;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Puts the escrow on a linear vesting schedule. The recipient then draws
+    /// funds with `claim_vested` as they vest, and milestone releases are
+    /// disabled. Must be called by the depositor before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `vesting` - Vesting schedule; a zero duration removes it
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    /// * `InvalidVestingSchedule` - If the cliff is longer than the duration
+    pub fn set_vesting(env: Env, escrow_id: u64, vesting: VestingSchedule) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        if vesting.cliff > vesting.duration {
+            return Err(Error::InvalidVestingSchedule);
+        }
+
+        escrow.vesting = vesting;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Returns the vested amount the recipient can claim right now, before fees.
+    /// Zero for unfunded, closed or non-vesting escrows.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    pub fn releasable_amount(env: Env, escrow_id: u64) -> Result<i128, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        releasable_vested(&env, &escrow)
+    }
+
+    /// Recipient claims everything vested so far, minus the platform fee.
+    /// Once the schedule has fully vested, every milestone is marked released.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `InvalidVestingSchedule` - If the escrow has no vesting schedule
+    /// * `ZeroAmount` - If nothing new has vested
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `RecipientNotWhitelisted` - If the payee is outside the escrow's recipient whitelist
+    pub fn claim_vested(env: Env, escrow_id: u64) -> Result<i128, Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify recipient authorization
+        escrow.recipient.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // The depositor or admin may hold releases on this escrow
+        ensure_not_escrow_paused(&escrow)?;
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+        if !is_vesting(&escrow) {
+            return Err(Error::InvalidVestingSchedule);
+        }
+
+        let amount = releasable_vested(&env, &escrow)?;
+        if amount == 0 {
+            return Err(Error::ZeroAmount);
+        }
+
+        // Split the claim into recipient payout and platform fee
+        let (treasury, fee_bps) = Self::get_config(env.clone())?;
+        let fee = calculate_fee(amount, fee_bps)?;
+        let payout = amount
+            .checked_sub(fee)
+            .ok_or(Error::InvalidMilestoneAmount)?;
+
+        add_released(&mut escrow, amount)?;
+        reduce_tvl(&env, amount);
+
+        // Fully vested escrows have paid out every milestone
+        if escrow.total_released == escrow.total_amount {
+            let now = env.ledger().timestamp();
+            for index in 0..escrow.milestones.len() {
+                let mut milestone = milestone_at(&escrow, index)?;
+                if !is_settled(&milestone) {
+                    milestone.status = MilestoneStatus::Released;
+                    milestone.released_at = Some(now);
+                    escrow.milestones.set(index, milestone);
+                }
+            }
+        }
+
+        ensure_whitelisted(&escrow, &escrow.recipient)?;
+        let token_client = token::Client::new(&env, &escrow.token_address);
+        safe_transfer(
+            &token_client,
+            &env.current_contract_address(),
+            &escrow.recipient,
+            &payout,
+        )?;
+        if fee > 0 {
+            safe_transfer(
+                &token_client,
+                &env.current_contract_address(),
+                &treasury,
+                &fee,
+            )?;
+            record_fee(&env, &escrow.token_address, fee);
+        }
+
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(&env, symbol_short!("vested"), escrow_id, (amount, fee));
+
+        exit(&env);
+
+        Ok(amount)
+    }
+
+    /// Pauses releases on a single escrow, recording why. Unlike a freeze,
+    /// other actions remain available: disputes can still be raised, though
+    /// resolving them pays out and so waits until the escrow resumes.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `caller` - The escrow's depositor or the contract admin
+    /// * `reason` - Short code explaining the pause
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If caller is neither the depositor nor the admin
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is completed or cancelled
+    pub fn pause_escrow(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        reason: Symbol,
+    ) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        set_escrow_pause(&env, escrow_id, caller, Some(reason))
+    }
+
+    /// Resumes releases on an escrow paused with `pause_escrow`.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `caller` - The escrow's depositor or the contract admin
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If caller is neither the depositor nor the admin
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is completed or cancelled
+    pub fn resume_escrow(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        set_escrow_pause(&env, escrow_id, caller, None)
+    }
+
+    /// Freezes a single escrow, blocking every mutation until it is unfrozen.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `caller` - Contract admin or the escrow's arbiter
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If caller is neither the admin nor the arbiter
+    pub fn freeze_escrow(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        set_frozen(&env, escrow_id, caller, true)
+    }
+
+    /// Lifts a freeze placed by `freeze_escrow`.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `caller` - Contract admin or the escrow's arbiter
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If caller is neither the admin nor the arbiter
+    pub fn unfreeze_escrow(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        set_frozen(&env, escrow_id, caller, false)
+    }
+
+    /// Returns the escrow's milestones as they stood when the given
+    /// milestone was first disputed, unaffected by anything that happened
+    /// afterwards, including disputes on other milestones.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the disputed milestone
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    /// * `MilestoneNotDisputed` - If the milestone has never been disputed
+    pub fn dispute_snapshot(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<DisputeSnapshot, Error> {
+        load_public_escrow(&env, escrow_id)?;
+        env.storage()
+            .persistent()
+            .get(&get_snapshot_key(escrow_id, milestone_index))
+            .ok_or(Error::MilestoneNotDisputed)
+    }
+
+    /// Returns the dispute raised on a milestone, or `None` if it was never
+    /// disputed. Resolved disputes are returned with `resolved` set.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    /// * `MilestoneNotFound` - If index is out of bounds
+    pub fn dispute_details(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<Option<DisputeInfo>, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+        validate_index(&escrow, milestone_index)?;
+
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&get_dispute_key(escrow_id, milestone_index)))
+    }
+
+    /// Returns a milestone along with its index and the escrow's status, so
+    /// an arbiter has the full picture for a resolution in one call.
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `UnauthorizedAccess` - If the escrow is private
+    /// * `MilestoneNotFound` - If index is out of bounds
+    pub fn milestone_context(
+        env: Env,
+        escrow_id: u64,
+        index: u32,
+    ) -> Result<MilestoneContext, Error> {
+        let escrow = load_public_escrow(&env, escrow_id)?;
+
+        let milestone = milestone_at(&escrow, index)?;
+        let is_disputed = milestone.status == MilestoneStatus::Disputed;
+
+        Ok(MilestoneContext {
+            milestone,
+            index,
+            escrow_status: escrow.status,
+            is_disputed,
+        })
+    }
+
+    /// Arbiter resolves a disputed milestone, either releasing it to the
+    /// recipient net of the platform fee or refunding it in full to the
+    /// depositor.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the disputed milestone
+    /// * `release_to_recipient` - `true` to pay the recipient, `false` to refund the depositor
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `UnauthorizedAccess` - If the escrow has no arbiter
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `MilestoneNotDisputed` - If milestone is not under dispute
+    /// * `ArbiterCapExceeded` - If the resolution would exceed the arbiter's cap
+    /// * `InsufficientBalance` - If the amount exceeds this escrow's remaining balance
+    /// * `TreasuryNotInitialized` - If releasing and the contract is not initialized
+    /// * `RecipientNotWhitelisted` - If the payee is outside the escrow's recipient whitelist
+    ///
+    /// The dispute fee goes back to the disputer if the resolution favors them,
+    /// and to the other party otherwise.
+    pub fn resolve_dispute(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+        release_to_recipient: bool,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+
+        // Verify arbiter authorization
+        let arbiter = escrow.arbiter.clone().ok_or(Error::UnauthorizedAccess)?;
+        arbiter.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Resolutions move funds, so they wait out a pause like releases do
+        ensure_not_escrow_paused(&escrow)?;
+
+        // Check escrow is active
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Load the milestone, rejecting an out-of-range index
+        let milestone = milestone_at(&escrow, milestone_index)?;
+
+        if milestone.status != MilestoneStatus::Disputed {
+            return Err(Error::MilestoneNotDisputed);
+        }
+
+        // Bound the total amount the arbiter can redirect
+        let moved = escrow
+            .arbiter_moved
+            .checked_add(milestone.amount)
+            .ok_or(Error::ArbiterCapExceeded)?;
+        if moved > escrow.arbiter_cap {
+            return Err(Error::ArbiterCapExceeded);
+        }
+
+        escrow.arbiter_moved = moved;
+        let paid = apply_resolution(
+            &env,
+            &mut escrow,
+            escrow_id,
+            milestone_index,
+            release_to_recipient,
+        )?;
+
+        // Save updated escrow
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        if release_to_recipient {
+            notify_release(&env, &escrow, escrow_id, milestone_index, paid);
+        }
+
+        // Emit event for dispute resolution
+        publish_event(
+            &env,
+            symbol_short!("resolved"),
+            escrow_id,
+            (milestone_index, arbiter, release_to_recipient),
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Applies the escrow's default outcome to a dispute left unresolved
+    /// past its window. Callable by anyone; no arbiter cap applies.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the disputed milestone
+    ///
+    /// # Errors
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `MilestoneNotFound` - If milestone index is invalid
+    /// * `MilestoneNotDisputed` - If the milestone is not under dispute
+    /// * `ActionTooSoon` - If no window is set or it has not yet elapsed
+    /// * `TreasuryNotInitialized` - If releasing and the contract is not initialized
+    pub fn resolve_stale_dispute(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // A default outcome pays out too, so it also waits for a resume
+        ensure_not_escrow_paused(&escrow)?;
+
+        // Check escrow is active
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Load the milestone, rejecting an out-of-range index
+        let milestone = milestone_at(&escrow, milestone_index)?;
+
+        if milestone.status != MilestoneStatus::Disputed {
+            return Err(Error::MilestoneNotDisputed);
+        }
+
+        let dispute: DisputeInfo = env
+            .storage()
+            .persistent()
+            .get(&get_dispute_key(escrow_id, milestone_index))
+            .ok_or(Error::MilestoneNotDisputed)?;
+
+        // The arbiter keeps the dispute until the window has fully elapsed
+        if escrow.dispute_window == 0
+            || env.ledger().timestamp() <= dispute.disputed_at.saturating_add(escrow.dispute_window)
+        {
+            return Err(Error::ActionTooSoon);
+        }
+
+        let release_to_recipient = escrow.dispute_default == DisputeDefault::Recipient;
+        let paid = apply_resolution(
+            &env,
+            &mut escrow,
+            escrow_id,
+            milestone_index,
+            release_to_recipient,
+        )?;
+
+        // Save updated escrow
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        if release_to_recipient {
+            notify_release(&env, &escrow, escrow_id, milestone_index, paid);
+        }
+
+        publish_event(
+            &env,
+            symbol_short!("stale_res"),
+            escrow_id,
+            (milestone_index, release_to_recipient),
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Cancels an escrow before any milestones are released.
+    /// Returns all funds to the depositor, or to `refund_to` when provided.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `refund_to` - Optional address receiving the refund instead of the depositor
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `UnauthorizedAccess` - If caller is not the depositor
+    /// * `EscrowNotActive` - If escrow is already completed or cancelled
+    /// * `MilestoneAlreadyReleased` - If any milestone has been released
+    pub fn cancel_escrow(
+        env: Env,
+        escrow_id: u64,
+        refund_to: Option<Address>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+
+        // Verify authorization - the depositor also authorizes any redirect
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Closed escrows cannot be cancelled again
+        if escrow.status != EscrowStatus::Created && escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Verify no milestones have been released
+        if escrow.total_released > 0 {
+            return Err(Error::MilestoneAlreadyReleased);
+        }
+
+        // If escrow was funded (Active status), refund the depositor
+        let remaining = escrow_balance(&escrow, &escrow.token_address);
+        if escrow.status == EscrowStatus::Active {
+            let token_client = token::Client::new(&env, &escrow.token_address);
+            let refund_to = refund_to.unwrap_or(escrow.depositor.clone());
+
+            // Transfer all remaining funds to the refund address
+            safe_transfer(
+                &token_client,
+                &env.current_contract_address(), // from (contract)
+                &refund_to,                      // to (depositor or chosen address)
+                &remaining,                      // everything not yet paid out
+            )?;
+            escrow.total_refunded = escrow
+                .total_refunded
+                .checked_add(remaining)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+
+            // Refunded funds no longer count towards TVL
+            reduce_tvl(&env, remaining);
+        }
+
+        // Disputes cut short by the cancellation get their fees back
+        refund_open_dispute_fees(&env, &escrow, escrow_id)?;
+
+        // The recipient did nothing wrong, so a posted bond goes back to them
+        return_bond(&env, &mut escrow)?;
+
+        // Update status
+        escrow.status = EscrowStatus::Cancelled;
+        close_request(&env, escrow_id);
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        // Emit event for cancellation
+        publish_event(&env, symbol_short!("cancelled"), escrow_id, remaining);
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Sets the bond the recipient must post to accept the escrow.
+    /// Must be called by the depositor before the escrow is funded.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `amount` - Bond amount in the escrow's token; zero removes the requirement
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowAlreadyFunded` - If escrow is no longer in Created state
+    /// * `BondAlreadyPosted` - If the recipient already posted a bond
+    /// * `InvalidBondAmount` - If the amount is negative
+    pub fn set_recipient_bond(env: Env, escrow_id: u64, amount: i128) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Terms are fixed once funds are deposited
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+        if escrow.bond_status == BondStatus::Posted {
+            return Err(Error::BondAlreadyPosted);
+        }
+        if amount < 0 {
+            return Err(Error::InvalidBondAmount);
+        }
+
+        escrow.recipient_bond = amount;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        Ok(())
+    }
+
+    /// Recipient accepts the escrow by posting the required bond.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is completed or cancelled
+    /// * `InvalidBondAmount` - If the escrow requires no bond
+    /// * `BondAlreadyPosted` - If the bond was already posted
+    pub fn post_bond(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify recipient authorization
+        escrow.recipient.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        if escrow.status != EscrowStatus::Created && escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+        if escrow.recipient_bond == 0 {
+            return Err(Error::InvalidBondAmount);
+        }
+        if escrow.bond_status != BondStatus::Unposted {
+            return Err(Error::BondAlreadyPosted);
+        }
+
+        // Pull the bond from the recipient into the contract
+        let token_client = token::Client::new(&env, &escrow.token_address);
+        safe_transfer(
+            &token_client,
+            &escrow.recipient,
+            &env.current_contract_address(),
+            &escrow.recipient_bond,
+        )?;
+
+        escrow.bond_status = BondStatus::Posted;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("bonded"),
+            escrow_id,
+            escrow.recipient_bond,
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Recipient backs out of the escrow. All unreleased funds return to the
+    /// depositor, and any posted bond is forfeited to the depositor as a penalty.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is completed or cancelled
+    pub fn recipient_cancel(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Verify recipient authorization
+        escrow.recipient.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        if escrow.status != EscrowStatus::Created && escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        let token_client = token::Client::new(&env, &escrow.token_address);
+
+        // Refund whatever the depositor has not yet paid out
+        let remaining = escrow_balance(&escrow, &escrow.token_address);
+        if remaining > 0 {
+            safe_transfer(
+                &token_client,
+                &env.current_contract_address(),
+                &escrow.depositor,
+                &remaining,
+            )?;
+            escrow.total_refunded = escrow
+                .total_refunded
+                .checked_add(remaining)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            reduce_tvl(&env, remaining);
+        }
+
+        // Disputes cut short by the cancellation get their fees back
+        refund_open_dispute_fees(&env, &escrow, escrow_id)?;
+
+        // Forfeit the recipient's bond to the depositor
+        let mut forfeited: i128 = 0;
+        if escrow.bond_status == BondStatus::Posted {
+            forfeited = escrow.recipient_bond;
+            safe_transfer(
+                &token_client,
+                &env.current_contract_address(),
+                &escrow.depositor,
+                &forfeited,
+            )?;
+            escrow.bond_status = BondStatus::Forfeited;
+        }
+
+        // Update status
+        escrow.status = EscrowStatus::Cancelled;
+        close_request(&env, escrow_id);
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("rcp_cncl"),
+            escrow_id,
+            (remaining, forfeited),
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Cancels a single pending milestone with the agreement of both
+    /// parties, refunding its amount to the depositor and marking it Refunded.
+    /// The milestone is dropped from the escrow's scope: its amount is zeroed
+    /// and `total_amount` shrinks by the same value, so the refund is not
+    /// counted in `total_refunded`. The rest of the escrow carries on unchanged.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `milestone_index` - Index of the milestone to cancel
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `VestingEscrow` - If the escrow pays out through a vesting schedule
+    /// * `MilestoneNotFound` - If index is out of bounds
+    /// * `MilestoneAlreadyReleased` - If the milestone was already paid out
+    /// * `MilestoneDisputed` - If the milestone is under dispute
+    /// * `MilestoneAlreadyStarted` - If the recipient has started work on it
+    /// * `InsufficientBalance` - If the refund exceeds this escrow's remaining balance
+    pub fn mutual_cancel_milestone(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+        let mut escrow = load_escrow(&env, escrow_id)?;
+
+        // Scope reductions need both parties to sign
+        escrow.depositor.require_auth();
+        escrow.recipient.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Check escrow is active
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Vesting escrows pay out through claim_vested only
+        ensure_not_vesting(&escrow)?;
+
+        // Load the milestone, rejecting an out-of-range index
+        let mut milestone = milestone_at(&escrow, milestone_index)?;
+
+        match milestone.status {
+            MilestoneStatus::Pending => {}
+            MilestoneStatus::InProgress => return Err(Error::MilestoneAlreadyStarted),
+            MilestoneStatus::Disputed => return Err(Error::MilestoneDisputed),
+            MilestoneStatus::Released | MilestoneStatus::Refunded => {
+                return Err(Error::MilestoneAlreadyReleased);
+            }
+        }
+
+        // Ensure this escrow alone holds enough of the token to cover the refund
+        ensure_escrow_balance(&escrow, &escrow.token_address, milestone.amount)?;
+
+        // Scope reduction: the milestone no longer counts towards the escrow
+        let amount = milestone.amount;
+        milestone.amount = 0;
+        milestone.status = MilestoneStatus::Refunded;
+        escrow.milestones.set(milestone_index, milestone);
+        escrow.total_amount = escrow
+            .total_amount
+            .checked_sub(amount)
+            .ok_or(Error::InvalidMilestoneAmount)?;
+        reduce_tvl(&env, amount);
+
+        let token_client = token::Client::new(&env, &escrow.token_address);
+        safe_transfer(
+            &token_client,
+            &env.current_contract_address(),
+            &escrow.depositor,
+            &amount,
+        )?;
+
+        // Save updated escrow
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        publish_event(
+            &env,
+            symbol_short!("ms_cancel"),
+            escrow_id,
+            (milestone_index, amount),
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Settles an escrow early: releases the listed milestones to the recipient,
+    /// refunds every other unreleased milestone to the depositor and marks the
+    /// escrow as completed. Releases are charged the platform fee and gated
+    /// like `release_milestone`, so with a release cooldown set at most one
+    /// milestone can be listed.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    /// * `release_indices` - Indices of the milestones to release to the recipient
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `EscrowPaused` - If releases on the escrow are paused
+    /// * `UnauthorizedAccess` - If caller is not the depositor
+    /// * `EscrowNotActive` - If escrow is not in Active state
+    /// * `VestingEscrow` - If the escrow pays out through a vesting schedule
+    /// * `MilestoneNotFound` - If any index is out of bounds
+    /// * `MilestoneAlreadyReleased` - If a listed milestone was already paid out
+    /// * `MilestoneDisputed` - If any unsettled milestone is under dispute
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `DependencyNotMet` - If a listed milestone's prerequisite is not released
+    /// * `ConditionsNotMet` - If the escrow's verifier or price oracle rejects the release
+    /// * `ActionTooSoon` - If the action interval or release cooldown has not elapsed
+    /// * `InsufficientBalance` - If the escrow holds too little to cover the payouts
+    /// * `RecipientNotWhitelisted` - If the payee is outside the escrow's recipient whitelist
+    pub fn settle_escrow(env: Env, escrow_id: u64, release_indices: Vec<u32>) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // The depositor or admin may hold releases on this escrow
+        ensure_not_escrow_paused(&escrow)?;
+
+        // Check escrow is active
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Vesting escrows pay out through claim_vested only
+        ensure_not_vesting(&escrow)?;
+
+        let (treasury, fee_bps) = Self::get_config(env.clone())?;
+
+        // Releases pass the same gates as `release_milestone`
+        if !release_indices.is_empty() {
+            ensure_conditions_met(&env, &escrow, escrow_id)?;
+        }
+
+        // Throttle rapid state changes on contentious escrows
+        record_action(&env, &mut escrow)?;
+
+        // Release the listed milestones, net of the platform fee
+        let mut to_recipient: i128 = 0;
+        let mut payouts: Vec<(u32, i128)> = Vec::new(&env);
+        for index in release_indices.iter() {
+            let milestone = milestone_at(&escrow, index)?;
+
+            if is_settled(&milestone) {
+                return Err(Error::MilestoneAlreadyReleased);
+            }
+            if milestone.status == MilestoneStatus::Disputed {
+                return Err(Error::MilestoneDisputed);
+            }
+            ensure_dependency_met(&escrow, &milestone)?;
+
+            // Each release observes the escrow's cooldown
+            record_release(&env, &mut escrow)?;
+
+            let plan = pay_out(
+                &env,
+                &mut escrow,
+                escrow_id,
+                index,
+                &treasury,
+                fee_bps,
+                true,
+            )?;
+            to_recipient = to_recipient
+                .checked_add(plan.to_recipient)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            payouts.push_back((index, plan.to_recipient));
+        }
+
+        // Refund everything else that is still outstanding
+        let mut to_depositor: i128 = 0;
+        for index in 0..escrow.milestones.len() {
+            let mut milestone = milestone_at(&escrow, index)?;
+
+            if is_settled(&milestone) {
+                continue;
+            }
+            if milestone.status == MilestoneStatus::Disputed {
+                return Err(Error::MilestoneDisputed);
+            }
+
+            to_depositor = to_depositor
+                .checked_add(milestone.amount)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            milestone.status = MilestoneStatus::Refunded;
+            escrow.milestones.set(index, milestone);
+        }
+
+        if to_depositor > 0 {
+            ensure_escrow_balance(&escrow, &escrow.token_address, to_depositor)?;
+            escrow.total_refunded = escrow
+                .total_refunded
+                .checked_add(to_depositor)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            reduce_tvl(&env, to_depositor);
+
+            let token_client = token::Client::new(&env, &escrow.token_address);
+            safe_transfer(
+                &token_client,
+                &env.current_contract_address(),
+                &escrow.depositor,
+                &to_depositor,
+            )?;
+        }
+
+        // A completed escrow owes the recipient its bond back
+        return_bond(&env, &mut escrow)?;
+
+        // Update status
+        escrow.status = EscrowStatus::Completed;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        for (index, payout) in payouts.iter() {
+            notify_release(&env, &escrow, escrow_id, index, payout);
+        }
+
+        // Emit event for settlement
+        publish_event(
+            &env,
+            symbol_short!("settled"),
+            escrow_id,
+            (to_recipient, to_depositor),
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+
+    /// Rewrites an escrow stored in the original layout into the current one (admin only).
+    /// New fields take their defaults: nothing refunded, no arbiter and an unknown (zero) creation time.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow to migrate
+    ///
+    /// # Errors
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `TreasuryNotInitialized` - If contract not initialized
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `AlreadyMigrated` - If the escrow is already stored in the current layout
+    pub fn migrate_escrow(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_unlocked(&env)?;
+
+        require_admin(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        // Escrows in the current layout carry `total_refunded`, which the
+        // original layout lacks; decoding one as `EscrowV1` would trap
+        let stored: Map<Symbol, Val> = env
+            .storage()
+            .persistent()
+            .get(&storage_key)
+            .ok_or(Error::EscrowNotFound)?;
+        if stored.contains_key(Symbol::new(&env, "total_refunded")) {
+            return Err(Error::AlreadyMigrated);
+        }
+        let legacy =
+            EscrowV1::try_from_val(&env, stored.as_val()).map_err(|_| Error::EscrowNotFound)?;
+
+        let mut milestones = Vec::new(&env);
+        for milestone in legacy.milestones.iter() {
+            milestones.push_back(Milestone {
+                amount: milestone.amount,
+                status: milestone.status,
+                description: milestone.description,
+                depends_on: None,
+                released_at: None,
+                recipient: None,
+                deadline: None,
+                metadata_uri: None,
+            });
+        }
+
+        let escrow = Escrow {
+            depositor: legacy.depositor,
+            recipient: legacy.recipient,
+            token_address: legacy.token_address,
+            total_amount: legacy.total_amount,
+            total_released: legacy.total_released,
+            milestones,
+            token: legacy.token,
+            status: legacy.status,
+            deadline: legacy.deadline,
+            total_refunded: 0,
+            arbiter: None,
+            arbiter_cap: 0,
+            arbiter_moved: 0,
+            created_at: 0,
+            private: false,
+            verifier: None,
+            frozen: false,
+            min_action_interval: 0,
+            last_action_ledger: None,
+            recipient_bond: 0,
+            bond_status: BondStatus::Unposted,
+            category: symbol_short!("general"),
+            recipient_signers: Vec::new(&env),
+            recipient_threshold: 0,
+            release_cooldown: 0,
+            last_release_ledger: None,
+            vesting: NO_VESTING,
+            require_proof: false,
+            funding_deadline: None,
+            pause_reason: None,
+            quiet: false,
+            recipient_whitelist: Vec::new(&env),
+            callback: None,
+            dispute_window: 0,
+            dispute_default: DisputeDefault::Depositor,
+            token_symbol: None,
+            token_decimals: None,
+            auto_release: false,
+        };
+
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        add_to_category(&env, &escrow.category, escrow_id);
+        add_to_token_index(&env, &escrow.token_address, escrow_id);
+        add_to_depositor_index(&env, &escrow.depositor, escrow_id);
+
+        // Legacy escrows predate the TVL counter, so count what they still hold
+        if escrow.status == EscrowStatus::Active {
+            add_tvl(&env, outstanding_amount(&escrow));
+        }
+
+        Ok(())
+    }
+
+    /// Marks an escrow as completed after all milestones are released.
+    /// A bond the recipient posted is handed back.
+    ///
+    /// # Arguments
+    /// * `escrow_id` - Identifier of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - If the contract is paused
+    /// * `ReentrancyDetected` - If called back into while another call moves funds
+    /// * `EscrowNotFound` - If escrow doesn't exist
+    /// * `EscrowFrozen` - If the escrow is frozen
+    /// * `UnauthorizedAccess` - If caller is not the depositor
+    /// * `EscrowNotActive` - If escrow is not in Active state or not all
+    ///   milestones are released
+    pub fn complete_escrow(env: Env, escrow_id: u64) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        enter(&env)?;
+
+        let storage_key = get_storage_key(escrow_id);
+
+        let mut escrow: Escrow = env
             .storage()
             .persistent()
             .get(&storage_key)
             .ok_or(Error::EscrowNotFound)?;
 
-        // Verify authorization
-        escrow.depositor.require_auth();
+        // Verify authorization
+        escrow.depositor.require_auth();
+
+        // Frozen escrows reject all mutations
+        ensure_not_frozen(&escrow)?;
+
+        // Only a funded, still open escrow can be completed
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // Verify all milestones are released
+        if !verify_all_released(&escrow.milestones) {
+            return Err(Error::EscrowNotActive);
+        }
+
+        // A completed escrow owes the recipient its bond back
+        return_bond(&env, &mut escrow)?;
+
+        // Update status
+        escrow.status = EscrowStatus::Completed;
+        env.storage().persistent().set(&storage_key, &escrow);
+
+        // Extend TTL
+        extend_escrow_ttl(&env, escrow_id);
+
+        // Emit event for completion
+        publish_event(
+            &env,
+            symbol_short!("completed"),
+            escrow_id,
+            escrow.total_released,
+        );
+
+        exit(&env);
+
+        Ok(())
+    }
+}
+
+// Returns the admin, falling back to the treasury for contracts initialized before admins existed
+fn get_admin(env: &Env) -> Option<Address> {
+    let storage = env.storage().instance();
+    storage
+        .get(&symbol_short!("admin"))
+        .or_else(|| storage.get(&symbol_short!("treasury")))
+}
+
+// Loads the admin and requires its authorization
+fn require_admin(env: &Env) -> Result<Address, Error> {
+    let admin = get_admin(env).ok_or(Error::TreasuryNotInitialized)?;
+    admin.require_auth();
+    Ok(admin)
+}
+
+fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("paused"))
+        .unwrap_or(false)
+}
+
+// Rejects escrow mutations while the contract is paused
+fn ensure_not_paused(env: &Env) -> Result<(), Error> {
+    if is_paused(env) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
+
+fn get_allowed_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("tokens"))
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_allowed_creators(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("creators"))
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_max_milestones(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("max_ms"))
+        .unwrap_or(DEFAULT_MAX_MILESTONES)
+}
+
+// Takes the contract-wide lock held while a call moves funds, so a token or
+// verifier cannot call back into the contract mid-update. The lock needs no
+// release on error paths because a failed call rolls back all storage writes.
+fn enter(env: &Env) -> Result<(), Error> {
+    ensure_unlocked(env)?;
+    env.storage()
+        .instance()
+        .set(&symbol_short!("locked"), &true);
+    Ok(())
+}
+
+// Rejects a call made while another call holds the lock taken by `enter`.
+// Mutators that make no external calls cannot be re-entered themselves, so
+// they only check the lock instead of taking it.
+fn ensure_unlocked(env: &Env) -> Result<(), Error> {
+    let locked: bool = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("locked"))
+        .unwrap_or(false);
+    if locked {
+        return Err(Error::ReentrancyDetected);
+    }
+    Ok(())
+}
+
+// Releases the lock taken by `enter`
+fn exit(env: &Env) {
+    env.storage().instance().remove(&symbol_short!("locked"));
+}
+
+// Restricts escrow creation to vetted accounts when an allowlist is configured
+fn ensure_creator_allowed(env: &Env, creator: &Address) -> Result<(), Error> {
+    let allowed_creators = get_allowed_creators(env);
+    if !allowed_creators.is_empty() && !allowed_creators.contains(creator) {
+        return Err(Error::CreatorNotAllowed);
+    }
+    Ok(())
+}
+
+// Returns the per-escrow amount cap, zero when unlimited
+fn get_max_escrow_amount(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("max_amt"))
+        .unwrap_or(0)
+}
+
+// Validates and stores a new unfunded escrow; shared by `create_escrow` and
+// `request_escrow`, which authenticate and vet the proposing party first
+fn store_new_escrow(
+    env: &Env,
+    escrow_id: u64,
+    depositor: Address,
+    recipient: Address,
+    token_address: Address,
+    milestones: Vec<Milestone>,
+    deadline: u64,
+) -> Result<Escrow, Error> {
+    // Validate no self-dealing (depositor cannot be recipient)
+    if depositor == recipient {
+        return Err(Error::SelfDealing);
+    }
+
+    // Restrict escrows to vetted tokens when an allowlist is configured
+    let allowed_tokens = get_allowed_tokens(env);
+    if !allowed_tokens.is_empty() && !allowed_tokens.contains(&token_address) {
+        return Err(Error::TokenNotAllowed);
+    }
+
+    // Check if escrow already exists
+    let storage_key = get_storage_key(escrow_id);
+    if env.storage().persistent().has(&storage_key) {
+        return Err(Error::EscrowAlreadyExists);
+    }
+
+    // Validate milestones and calculate total
+    let total_amount = validate_milestones(
+        &milestones,
+        get_max_milestones(env),
+        get_min_milestone_amount(env),
+    )?;
+
+    // Enforce the operator's per-escrow exposure cap
+    let max_escrow_amount = get_max_escrow_amount(env);
+    if max_escrow_amount > 0 && total_amount > max_escrow_amount {
+        return Err(Error::AmountExceedsLimit);
+    }
+
+    // Initialize all milestones to Pending status
+    let mut initialized_milestones = Vec::new(env);
+    for milestone in milestones.iter() {
+        // Milestone payees are held to the same self-dealing rule
+        if milestone.recipient.as_ref() == Some(&depositor) {
+            return Err(Error::SelfDealing);
+        }
+
+        let mut m = milestone.clone();
+        m.status = MilestoneStatus::Pending;
+        m.released_at = None;
+        initialized_milestones.push_back(m);
+    }
+
+    // Create the escrow in Created state (not yet funded)
+    let escrow = Escrow {
+        depositor: depositor.clone(),
+        recipient,
+        token_address: token_address.clone(),
+        total_amount,
+        total_released: 0,
+        milestones: initialized_milestones,
+        token: token_address,
+        status: EscrowStatus::Created, // Initially Created, becomes Active after deposit
+        deadline,
+        total_refunded: 0,
+        arbiter: None,
+        arbiter_cap: 0,
+        arbiter_moved: 0,
+        created_at: env.ledger().timestamp(),
+        private: false,
+        verifier: None,
+        frozen: false,
+        min_action_interval: 0,
+        last_action_ledger: None,
+        recipient_bond: 0,
+        bond_status: BondStatus::Unposted,
+        category: symbol_short!("general"),
+        recipient_signers: Vec::new(env),
+        recipient_threshold: 0,
+        release_cooldown: 0,
+        last_release_ledger: None,
+        vesting: NO_VESTING,
+        require_proof: false,
+        funding_deadline: None,
+        pause_reason: None,
+        quiet: false,
+        recipient_whitelist: Vec::new(env),
+        callback: None,
+        dispute_window: 0,
+        dispute_default: DisputeDefault::Depositor,
+        token_symbol: None,
+        token_decimals: None,
+        auto_release: false,
+    };
+
+    // Save to persistent storage
+    env.storage().persistent().set(&storage_key, &escrow);
+
+    // Extend TTL for long-term storage
+    env.storage()
+        .persistent()
+        .extend_ttl(&storage_key, 100, 2_000_000);
+
+    add_to_category(env, &escrow.category, escrow_id);
+    add_to_token_index(env, &escrow.token_address, escrow_id);
+
+    Ok(escrow)
+}
+
+// Returns the smallest allowed milestone amount, zero when unchecked
+fn get_min_milestone_amount(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("min_amt"))
+        .unwrap_or(0)
+}
+
+// Returns the contract-wide TVL cap, zero when unlimited
+fn get_max_tvl(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("max_tvl"))
+        .unwrap_or(0)
+}
+
+// Returns the amount locked in funded escrows across the contract
+fn get_tvl(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("tvl"))
+        .unwrap_or(0)
+}
+
+// Counts newly deposited funds towards TVL
+fn add_tvl(env: &Env, amount: i128) {
+    let tvl = get_tvl(env).saturating_add(amount);
+    env.storage().instance().set(&symbol_short!("tvl"), &tvl);
+}
+
+// Drops funds that have left escrow from TVL
+fn reduce_tvl(env: &Env, amount: i128) {
+    let tvl = (get_tvl(env) - amount).max(0);
+    env.storage().instance().set(&symbol_short!("tvl"), &tvl);
+}
+
+// Storage key for the lifetime platform fees collected in a token
+fn get_fees_key(token: &Address) -> (Symbol, Address) {
+    (symbol_short!("fees"), token.clone())
+}
+
+// Key under which an escrow's oracle price condition is stored
+fn get_price_condition_key(escrow_id: u64) -> (Symbol, u64) {
+    (symbol_short!("price"), escrow_id)
+}
+
+// Adds a collected platform fee to the token's lifetime total
+fn record_fee(env: &Env, token: &Address, fee: i128) {
+    let key = get_fees_key(token);
+    let total: i128 = env.storage().instance().get(&key).unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&key, &total.saturating_add(fee));
+}
+
+// Returns the part of the escrow total not yet released or refunded
+fn outstanding_amount(escrow: &Escrow) -> i128 {
+    escrow.total_amount - escrow.total_released - escrow.total_refunded
+}
+
+// Returns the fee charged to raise a dispute, zero unless configured
+fn get_dispute_fee(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("disp_fee"))
+        .unwrap_or(0)
+}
+
+// Hands a posted recipient bond back to the recipient
+fn return_bond(env: &Env, escrow: &mut Escrow) -> Result<(), Error> {
+    if escrow.bond_status == BondStatus::Posted {
+        let token_client = token::Client::new(env, &escrow.token_address);
+        safe_transfer(
+            &token_client,
+            &env.current_contract_address(),
+            &escrow.recipient,
+            &escrow.recipient_bond,
+        )?;
+        escrow.bond_status = BondStatus::Returned;
+    }
+    Ok(())
+}
+
+// Returns held fees for disputes still open on a cancelled escrow
+fn refund_open_dispute_fees(env: &Env, escrow: &Escrow, escrow_id: u64) -> Result<(), Error> {
+    let token_client = token::Client::new(env, &escrow.token_address);
+    for (index, milestone) in escrow.milestones.iter().enumerate() {
+        if milestone.status != MilestoneStatus::Disputed {
+            continue;
+        }
+        let dispute_key = get_dispute_key(escrow_id, index as u32);
+        if let Some(mut dispute) = env
+            .storage()
+            .persistent()
+            .get::<_, DisputeInfo>(&dispute_key)
+            && !dispute.resolved
+            && dispute.fee > 0
+        {
+            safe_transfer(
+                &token_client,
+                &env.current_contract_address(),
+                &dispute.disputer,
+                &dispute.fee,
+            )?;
+            dispute.fee = 0;
+            env.storage().persistent().set(&dispute_key, &dispute);
+        }
+    }
+    Ok(())
+}
+
+// Publishes a lifecycle event as `("escrow", action, escrow_id)` so subscribers
+// can filter a single escrow's events by topic, and appends it to the escrow's
+// bounded history for clients that missed the event stream
+fn publish_event<D: IntoVal<Env, Val>>(env: &Env, action: Symbol, escrow_id: u64, data: D) {
+    record_event(env, action.clone(), escrow_id);
+
+    #[allow(deprecated)]
+    env.events()
+        .publish((symbol_short!("escrow"), action, escrow_id), data);
+}
+
+// Appends an event to the escrow's history, dropping the oldest beyond the limit
+fn record_event(env: &Env, action: Symbol, escrow_id: u64) {
+    let key = get_events_key(escrow_id);
+    let mut history: Vec<EscrowEventRecord> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    let sequence = match history.last() {
+        Some(last) => last.sequence + 1,
+        None => 0,
+    };
+    if history.len() >= MAX_RECENT_EVENTS {
+        history.pop_front();
+    }
+    history.push_back(EscrowEventRecord {
+        sequence,
+        action,
+        timestamp: env.ledger().timestamp(),
+    });
+
+    env.storage().persistent().set(&key, &history);
+    env.storage().persistent().extend_ttl(&key, 100, 2_000_000);
+}
+
+// Storage key for an escrow's recent event history
+fn get_events_key(escrow_id: u64) -> (Symbol, u64) {
+    (symbol_short!("events"), escrow_id)
+}
+
+// Storage key for an escrow's participant notes
+fn get_notes_key(escrow_id: u64) -> (Symbol, u64) {
+    (symbol_short!("notes"), escrow_id)
+}
+
+// Extends the TTL of an escrow and every record stored alongside it.
+// Release references are keyed by an arbitrary hash and cannot be
+// enumerated, so they keep the TTL they were written with.
+fn extend_escrow_ttl(env: &Env, escrow_id: u64) {
+    let storage_key = get_storage_key(escrow_id);
+    let escrow: Option<Escrow> = env.storage().persistent().get(&storage_key);
+    let Some(escrow) = escrow else {
+        return;
+    };
+    env.storage()
+        .persistent()
+        .extend_ttl(&storage_key, 100, 2_000_000);
 
-        // Verify all milestones are released
-        if !verify_all_released(&escrow.milestones) {
-            return Err(Error::EscrowNotActive);
-        }
+    extend_ttl_if_present(env, &get_events_key(escrow_id));
+    extend_ttl_if_present(env, &get_notes_key(escrow_id));
+    extend_ttl_if_present(env, &get_price_condition_key(escrow_id));
+    extend_ttl_if_present(env, &get_request_key(escrow_id));
 
-        // Update status
-        escrow.status = EscrowStatus::Completed;
-        env.storage().persistent().set(&storage_key, &escrow);
+    for index in 0..escrow.milestones.len() {
+        extend_ttl_if_present(env, &get_dispute_key(escrow_id, index));
+        extend_ttl_if_present(env, &get_snapshot_key(escrow_id, index));
+        extend_ttl_if_present(env, &get_proof_key(escrow_id, index));
+        extend_ttl_if_present(env, &get_approvals_key(escrow_id, index));
+        extend_ttl_if_present(env, &get_delivered_key(escrow_id, index));
+    }
+}
 
-        // Extend TTL
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+// Extends the TTL of a persistent entry if it exists
+fn extend_ttl_if_present<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let storage = env.storage().persistent();
+    if storage.has(key) {
+        storage.extend_ttl(key, 100, 2_000_000);
+    }
+}
 
-        Ok(())
+// Loads an escrow from persistent storage
+fn load_escrow(env: &Env, escrow_id: u64) -> Result<Escrow, Error> {
+    env.storage()
+        .persistent()
+        .get(&get_storage_key(escrow_id))
+        .ok_or(Error::EscrowNotFound)
+}
+
+// Loads an escrow for a public read, refusing private escrows
+fn load_public_escrow(env: &Env, escrow_id: u64) -> Result<Escrow, Error> {
+    let escrow = load_escrow(env, escrow_id)?;
+    if escrow.private {
+        return Err(Error::UnauthorizedAccess);
+    }
+    Ok(escrow)
+}
+
+// Returns whether the escrow exists and is private
+fn is_private(env: &Env, escrow_id: u64) -> bool {
+    load_escrow(env, escrow_id).is_ok_and(|escrow| escrow.private)
+}
+
+// Shared body of `freeze_escrow` and `unfreeze_escrow`
+fn set_frozen(env: &Env, escrow_id: u64, caller: Address, frozen: bool) -> Result<(), Error> {
+    let storage_key = get_storage_key(escrow_id);
+    let mut escrow = load_escrow(env, escrow_id)?;
+
+    // Verify authorization - only the admin or the escrow's arbiter
+    caller.require_auth();
+    if get_admin(env).as_ref() != Some(&caller) && escrow.arbiter.as_ref() != Some(&caller) {
+        return Err(Error::UnauthorizedAccess);
+    }
+
+    escrow.frozen = frozen;
+    env.storage().persistent().set(&storage_key, &escrow);
+
+    // Extend TTL
+    extend_escrow_ttl(env, escrow_id);
+
+    let action = if frozen {
+        symbol_short!("frozen")
+    } else {
+        symbol_short!("unfrozen")
+    };
+    publish_event(env, action, escrow_id, caller);
+
+    Ok(())
+}
+
+// Shared body of `pause_escrow` and `resume_escrow`
+fn set_escrow_pause(
+    env: &Env,
+    escrow_id: u64,
+    caller: Address,
+    reason: Option<Symbol>,
+) -> Result<(), Error> {
+    let storage_key = get_storage_key(escrow_id);
+    let mut escrow = load_escrow(env, escrow_id)?;
+
+    // Verify authorization - only the depositor or the admin
+    caller.require_auth();
+    if escrow.depositor != caller && get_admin(env).as_ref() != Some(&caller) {
+        return Err(Error::UnauthorizedAccess);
+    }
+
+    // Frozen escrows reject all mutations
+    ensure_not_frozen(&escrow)?;
+
+    if escrow.status != EscrowStatus::Created && escrow.status != EscrowStatus::Active {
+        return Err(Error::EscrowNotActive);
+    }
+
+    let action = if reason.is_some() {
+        symbol_short!("halted")
+    } else {
+        symbol_short!("resumed")
+    };
+    escrow.pause_reason = reason.clone();
+    env.storage().persistent().set(&storage_key, &escrow);
+
+    // Extend TTL
+    extend_escrow_ttl(env, escrow_id);
+
+    publish_event(env, action, escrow_id, (caller, reason));
+
+    Ok(())
+}
+
+// Enforces the escrow's minimum action interval and records the current ledger
+fn record_action(env: &Env, escrow: &mut Escrow) -> Result<(), Error> {
+    let current = env.ledger().sequence();
+    if let Some(last) = escrow.last_action_ledger
+        && current < last.saturating_add(escrow.min_action_interval)
+    {
+        return Err(Error::ActionTooSoon);
+    }
+    escrow.last_action_ledger = Some(current);
+    Ok(())
+}
+
+// Enforces the escrow's release cooldown and records the current ledger
+fn record_release(env: &Env, escrow: &mut Escrow) -> Result<(), Error> {
+    let current = env.ledger().sequence();
+    if let Some(last) = escrow.last_release_ledger
+        && current < last.saturating_add(escrow.release_cooldown)
+    {
+        return Err(Error::ActionTooSoon);
+    }
+    escrow.last_release_ledger = Some(current);
+    Ok(())
+}
+
+// Runs the gates of `release_milestone` without changing any state
+fn check_releasable(env: &Env, escrow_id: u64, milestone_index: u32) -> Result<(), Error> {
+    ensure_not_paused(env)?;
+
+    let mut escrow = load_public_escrow(env, escrow_id)?;
+    ensure_not_frozen(&escrow)?;
+    ensure_not_escrow_paused(&escrow)?;
+    if escrow.status != EscrowStatus::Active {
+        return Err(Error::EscrowNotActive);
+    }
+    ensure_not_vesting(&escrow)?;
+
+    let milestone = milestone_at(&escrow, milestone_index)?;
+    if is_settled(&milestone) {
+        return Err(Error::MilestoneAlreadyReleased);
+    }
+    if milestone.status == MilestoneStatus::Disputed {
+        return Err(Error::MilestoneDisputed);
+    }
+
+    VaultixEscrow::get_config(env.clone())?;
+    milestone_payee(&escrow, &milestone)?;
+    ensure_escrow_balance(&escrow, &escrow.token_address, milestone.amount)?;
+    ensure_dependency_met(&escrow, &milestone)?;
+    ensure_conditions_met(env, &escrow, escrow_id)?;
+
+    // Throttles are checked against this copy, which is never saved
+    record_action(env, &mut escrow)?;
+    record_release(env, &mut escrow)
+}
+
+// Rejects milestone releases on escrows that pay out by vesting
+fn ensure_not_vesting(escrow: &Escrow) -> Result<(), Error> {
+    if is_vesting(escrow) {
+        return Err(Error::VestingEscrow);
+    }
+    Ok(())
+}
+
+// Checks whether the escrow pays out through a vesting schedule
+fn is_vesting(escrow: &Escrow) -> bool {
+    escrow.vesting.duration > 0
+}
+
+// Computes the vested amount not yet claimed from an active vesting escrow
+fn releasable_vested(env: &Env, escrow: &Escrow) -> Result<i128, Error> {
+    if !is_vesting(escrow) || escrow.status != EscrowStatus::Active {
+        return Ok(0);
+    }
+    let schedule = escrow.vesting;
+
+    let now = env.ledger().timestamp();
+    let elapsed = now.saturating_sub(schedule.start);
+    let vested = if elapsed < schedule.cliff {
+        0
+    } else if elapsed >= schedule.duration {
+        escrow.total_amount
+    } else {
+        escrow
+            .total_amount
+            .checked_mul(elapsed as i128)
+            .ok_or(Error::InvalidMilestoneAmount)?
+            / schedule.duration as i128
+    };
+
+    Ok((vested - escrow.total_released - escrow.total_refunded).max(0))
+}
+
+// Rejects mutations on an escrow frozen by the admin or arbiter
+fn ensure_not_frozen(escrow: &Escrow) -> Result<(), Error> {
+    if escrow.frozen {
+        return Err(Error::EscrowFrozen);
+    }
+    Ok(())
+}
+
+// Ensures a milestone index falls within the escrow's milestones
+fn validate_index(escrow: &Escrow, milestone_index: u32) -> Result<(), Error> {
+    if milestone_index >= escrow.milestones.len() {
+        return Err(Error::MilestoneNotFound);
+    }
+    Ok(())
+}
+
+// Returns the milestone at the given index once the index is validated
+fn milestone_at(escrow: &Escrow, milestone_index: u32) -> Result<Milestone, Error> {
+    validate_index(escrow, milestone_index)?;
+    Ok(escrow.milestones.get_unchecked(milestone_index))
+}
+
+// Rejects releases while the escrow is paused by its depositor or the admin
+fn ensure_not_escrow_paused(escrow: &Escrow) -> Result<(), Error> {
+    if escrow.pause_reason.is_some() {
+        return Err(Error::EscrowPaused);
+    }
+    Ok(())
+}
+
+// Checks whether an address takes part in the escrow
+fn is_participant(escrow: &Escrow, address: &Address) -> bool {
+    find_party(escrow, address).is_some()
+}
+
+// Locates an address among the escrow's parties, returning its position in
+// `party_addresses` and its role
+fn find_party(escrow: &Escrow, address: &Address) -> Option<(u32, EscrowRole)> {
+    if *address == escrow.depositor {
+        Some((0, EscrowRole::Depositor))
+    } else if *address == escrow.recipient {
+        Some((1, EscrowRole::Recipient))
+    } else if escrow.arbiter.as_ref() == Some(address) {
+        Some((2, EscrowRole::Arbiter))
+    } else {
+        None
     }
 }
 
@@ -594,39 +5135,682 @@ fn get_storage_key(escrow_id: u64) -> (Symbol, u64) {
     (symbol_short!("escrow"), escrow_id)
 }
 
+// Identifies the paged index of escrow ids in a category
+fn get_category_key(category: &Symbol) -> (Symbol, Symbol) {
+    (symbol_short!("category"), category.clone())
+}
+
+// Storage key for the slot an escrow occupies in its category index
+fn get_category_slot_key(escrow_id: u64) -> (Symbol, u64) {
+    (symbol_short!("cat_slot"), escrow_id)
+}
+
+// Appends an escrow id to its category index
+fn add_to_category(env: &Env, category: &Symbol, escrow_id: u64) {
+    let slot = append_to_index(env, &get_category_key(category), escrow_id);
+    let slot_key = get_category_slot_key(escrow_id);
+    env.storage().persistent().set(&slot_key, &slot);
+    env.storage()
+        .persistent()
+        .extend_ttl(&slot_key, 100, 2_000_000);
+}
+
+// Drops an escrow id from its category index, leaving its slot empty so the
+// remaining ids keep their creation order
+fn remove_from_category(env: &Env, category: &Symbol, escrow_id: u64) {
+    let slot_key = get_category_slot_key(escrow_id);
+    let slot: Option<u32> = env.storage().persistent().get(&slot_key);
+    if let Some(slot) = slot {
+        let index = get_category_key(category).into_val(env);
+        env.storage()
+            .persistent()
+            .remove(&get_index_slot_key(index, slot));
+        env.storage().persistent().remove(&slot_key);
+    }
+}
+
+// Storage key for the number of slots ever used in a paged id index
+fn get_index_len_key(index: Val) -> (Symbol, Val) {
+    (symbol_short!("idx_len"), index)
+}
+
+// Storage key for one slot of a paged id index
+fn get_index_slot_key(index: Val, slot: u32) -> (Symbol, Val, u32) {
+    (symbol_short!("idx_slot"), index, slot)
+}
+
+// Appends an escrow id to a paged index and returns the slot it landed in.
+// Each id gets its own entry, so appending costs the same however large the
+// index grows.
+fn append_to_index<K: IntoVal<Env, Val>>(env: &Env, index: &K, escrow_id: u64) -> u32 {
+    let storage = env.storage().persistent();
+    let index: Val = index.into_val(env);
+    let len_key = get_index_len_key(index);
+    let slot: u32 = storage.get(&len_key).unwrap_or(0);
+
+    let slot_key = get_index_slot_key(index, slot);
+    storage.set(&slot_key, &escrow_id);
+    storage.extend_ttl(&slot_key, 100, 2_000_000);
+
+    storage.set(&len_key, &(slot + 1));
+    storage.extend_ttl(&len_key, 100, 2_000_000);
+    slot
+}
+
+// Identifies the paged index of escrow ids using a token
+fn get_token_index_key(token: &Address) -> (Symbol, Address) {
+    (symbol_short!("by_token"), token.clone())
+}
+
+// Appends an escrow id to its token's index
+fn add_to_token_index(env: &Env, token: &Address, escrow_id: u64) {
+    append_to_index(env, &get_token_index_key(token), escrow_id);
+}
+
+// Identifies the paged index of escrow ids funded by a depositor
+fn get_depositor_index_key(depositor: &Address) -> (Symbol, Address) {
+    (symbol_short!("by_dep"), depositor.clone())
+}
+
+// Appends an escrow id to its depositor's index
+fn add_to_depositor_index(env: &Env, depositor: &Address, escrow_id: u64) {
+    append_to_index(env, &get_depositor_index_key(depositor), escrow_id);
+}
+
+// Returns one page of a paged id index, reading at most MAX_PAGE_SIZE slots.
+// Emptied slots are skipped, so a page may hold fewer ids than requested.
+fn paginate<K: IntoVal<Env, Val>>(env: &Env, index: &K, start: u32, limit: u32) -> Vec<u64> {
+    let storage = env.storage().persistent();
+    let index: Val = index.into_val(env);
+    let len: u32 = storage.get(&get_index_len_key(index)).unwrap_or(0);
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
+
+    let mut ids = Vec::new(env);
+    for slot in start..end {
+        let id: Option<u64> = storage.get(&get_index_slot_key(index, slot));
+        if let Some(id) = id {
+            ids.push_back(id);
+        }
+    }
+    ids
+}
+
+// Storage key for the recipient-side approvals of a milestone's delivery
+fn get_approvals_key(escrow_id: u64, milestone_index: u32) -> (Symbol, u64, u32) {
+    (symbol_short!("approvals"), escrow_id, milestone_index)
+}
+
+// Storage key for the escrow snapshot taken when a milestone was first disputed
+fn get_snapshot_key(escrow_id: u64, milestone_index: u32) -> (Symbol, u64, u32) {
+    (symbol_short!("snapshot"), escrow_id, milestone_index)
+}
+
+// Storage key for the recipient's delivery proof on a milestone
+fn get_proof_key(escrow_id: u64, milestone_index: u32) -> (Symbol, u64, u32) {
+    (symbol_short!("proof"), escrow_id, milestone_index)
+}
+
+// Storage key for when the recipient last submitted a delivery on an
+// auto-releasing escrow
+fn get_delivered_key(escrow_id: u64, milestone_index: u32) -> (Symbol, u64, u32) {
+    (symbol_short!("dlv_at"), escrow_id, milestone_index)
+}
+
+// Storage key marking an external release reference as used on an escrow
+fn get_release_ref_key(escrow_id: u64, release_ref: &BytesN<32>) -> (Symbol, u64, BytesN<32>) {
+    (symbol_short!("rel_ref"), escrow_id, release_ref.clone())
+}
+
+// Storage key counting a recipient's unfunded escrow requests
+fn get_open_requests_key(recipient: &Address) -> (Symbol, Address) {
+    (symbol_short!("open_req"), recipient.clone())
+}
+
+// Storage key marking an escrow as an unfunded request, holding the recipient
+fn get_request_key(escrow_id: u64) -> (Symbol, u64) {
+    (symbol_short!("request"), escrow_id)
+}
+
+// Stops counting an escrow against its recipient's open requests once it is
+// funded or cancelled; a no-op for escrows not created by request
+fn close_request(env: &Env, escrow_id: u64) {
+    let request_key = get_request_key(escrow_id);
+    let Some(recipient) = env.storage().persistent().get::<_, Address>(&request_key) else {
+        return;
+    };
+    env.storage().persistent().remove(&request_key);
+
+    let open_key = get_open_requests_key(&recipient);
+    let open_requests: u32 = env.storage().persistent().get(&open_key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&open_key, &open_requests.saturating_sub(1));
+}
+
+// Storage key for the dispute raised on a milestone
+fn get_dispute_key(escrow_id: u64, milestone_index: u32) -> (Symbol, u64, u32) {
+    (symbol_short!("dispute"), escrow_id, milestone_index)
+}
+
 // Validates milestone vector and returns total amount
-fn validate_milestones(milestones: &Vec<Milestone>) -> Result<i128, Error> {
+fn validate_milestones(
+    milestones: &Vec<Milestone>,
+    max_milestones: u32,
+    min_amount: i128,
+) -> Result<i128, Error> {
     // Check vector size to prevent gas issues
-    if milestones.len() > 20 {
+    if milestones.len() > max_milestones {
         return Err(Error::VectorTooLarge);
     }
 
     let mut total: i128 = 0;
 
     // Validate each milestone and calculate total
-    for milestone in milestones.iter() {
+    for (index, milestone) in milestones.iter().enumerate() {
         if milestone.amount <= 0 {
             return Err(Error::ZeroAmount);
         }
 
+        // Dust milestones complicate fee math
+        if milestone.amount < min_amount {
+            return Err(Error::MilestoneTooSmall);
+        }
+
+        if let Some(uri) = &milestone.metadata_uri
+            && (uri.is_empty() || uri.len() > MAX_URI_LEN)
+        {
+            return Err(Error::InvalidMetadataUri);
+        }
+
+        // Dependencies may only point backwards, which rules out cycles
+        if let Some(dependency) = milestone.depends_on
+            && dependency as usize >= index
+        {
+            return Err(Error::InvalidDependency);
+        }
+
         total = total
             .checked_add(milestone.amount)
-            .ok_or(Error::InvalidMilestoneAmount)?;
+            .ok_or(Error::TotalAmountOverflow)?;
     }
 
     Ok(total)
 }
 
-// Checks if all milestones have been released
+// Checks if all milestones have been paid out, either released or refunded
 fn verify_all_released(milestones: &Vec<Milestone>) -> bool {
     for milestone in milestones.iter() {
-        if milestone.status != MilestoneStatus::Released {
+        if !is_settled(&milestone) {
             return false;
         }
     }
     true
 }
 
+// Cross-checks an escrow's totals and status against its milestones
+fn invariants_hold(escrow: &Escrow) -> bool {
+    let mut milestone_total: i128 = 0;
+    let mut released_total: i128 = 0;
+    let mut any_settled = false;
+    for milestone in escrow.milestones.iter() {
+        milestone_total = milestone_total.saturating_add(milestone.amount);
+        if milestone.status == MilestoneStatus::Released {
+            released_total = released_total.saturating_add(milestone.amount);
+        }
+        any_settled |= is_settled(&milestone);
+    }
+
+    if milestone_total != escrow.total_amount || escrow.total_released < 0 {
+        return false;
+    }
+    if escrow.total_released.saturating_add(escrow.total_refunded) > escrow.total_amount {
+        return false;
+    }
+
+    // Vesting pays out gradually and marks milestones only once fully vested
+    let released_matches = if is_vesting(escrow) {
+        released_total <= escrow.total_released
+    } else {
+        released_total == escrow.total_released
+    };
+    if !released_matches {
+        return false;
+    }
+
+    match escrow.status {
+        EscrowStatus::Created => {
+            escrow.total_released == 0 && escrow.total_refunded == 0 && !any_settled
+        }
+        EscrowStatus::Completed => verify_all_released(&escrow.milestones),
+        EscrowStatus::Active | EscrowStatus::Cancelled => true,
+    }
+}
+
+// A settled milestone has already paid out and can never move funds again
+fn is_settled(milestone: &Milestone) -> bool {
+    matches!(
+        milestone.status,
+        MilestoneStatus::Released | MilestoneStatus::Refunded
+    )
+}
+
+/// Returns the amount of `token` this escrow still holds in the contract.
+///
+/// The contract pools funds from every escrow, so releases must be bounded by
+/// the escrow's own bookkeeping rather than the contract's aggregate balance.
+/// An unfunded escrow, or a token other than the escrow's own, holds nothing.
+fn escrow_balance(escrow: &Escrow, token: &Address) -> i128 {
+    if escrow.status != EscrowStatus::Active || *token != escrow.token_address {
+        return 0;
+    }
+    escrow.total_amount - escrow.total_released - escrow.total_refunded
+}
+
+// Ensures the milestone's prerequisite, if any, has already been released
+fn ensure_dependency_met(escrow: &Escrow, milestone: &Milestone) -> Result<(), Error> {
+    if let Some(dependency) = milestone.depends_on {
+        let prerequisite = milestone_at(escrow, dependency)?;
+        if prerequisite.status != MilestoneStatus::Released {
+            return Err(Error::DependencyNotMet);
+        }
+    }
+    Ok(())
+}
+
+// Asks the escrow's verifier contract and price oracle, if any, whether
+// release conditions hold
+fn ensure_conditions_met(env: &Env, escrow: &Escrow, escrow_id: u64) -> Result<(), Error> {
+    if let Some(verifier) = &escrow.verifier
+        && !VerifierClient::new(env, verifier).check(&escrow_id)
+    {
+        return Err(Error::ConditionsNotMet);
+    }
+
+    let condition: Option<PriceCondition> = env
+        .storage()
+        .persistent()
+        .get(&get_price_condition_key(escrow_id));
+    if let Some(condition) = condition {
+        let price = PriceOracleClient::new(env, &condition.oracle).price(&condition.asset);
+        let holds = if condition.above {
+            price >= condition.threshold
+        } else {
+            price <= condition.threshold
+        };
+        if !holds {
+            return Err(Error::ConditionsNotMet);
+        }
+    }
+    Ok(())
+}
+
+// Settles a disputed milestone in one direction: pays it out net of the
+// platform fee or refunds it in full, then closes the dispute and routes its
+// fee. Returns what the winning party received: the recipient's payout after
+// the fee, or the full refund.
+fn apply_resolution(
+    env: &Env,
+    escrow: &mut Escrow,
+    escrow_id: u64,
+    milestone_index: u32,
+    release_to_recipient: bool,
+) -> Result<i128, Error> {
+    let mut milestone = milestone_at(escrow, milestone_index)?;
+
+    ensure_escrow_balance(escrow, &escrow.token_address, milestone.amount)?;
+
+    let token_client = token::Client::new(env, &escrow.token_address);
+    let paid = if release_to_recipient {
+        let (treasury, fee_bps) = VaultixEscrow::get_config(env.clone())?;
+        let plan = pay_out(
+            env,
+            escrow,
+            escrow_id,
+            milestone_index,
+            &treasury,
+            fee_bps,
+            true,
+        )?;
+        plan.to_recipient
+    } else {
+        milestone.status = MilestoneStatus::Refunded;
+        escrow.total_refunded = escrow
+            .total_refunded
+            .checked_add(milestone.amount)
+            .ok_or(Error::InvalidMilestoneAmount)?;
+        reduce_tvl(env, milestone.amount);
+        escrow.milestones.set(milestone_index, milestone.clone());
+
+        safe_transfer(
+            &token_client,
+            &env.current_contract_address(),
+            &escrow.depositor,
+            &milestone.amount,
+        )?;
+        milestone.amount
+    };
+
+    // Mark the dispute as resolved and settle its fee
+    let dispute_key = get_dispute_key(escrow_id, milestone_index);
+    if let Some(mut dispute) = env
+        .storage()
+        .persistent()
+        .get::<_, DisputeInfo>(&dispute_key)
+    {
+        let disputer_is_recipient = dispute.disputer == escrow.recipient;
+        dispute.upheld = disputer_is_recipient == release_to_recipient;
+        if dispute.fee > 0 {
+            let fee_to = if dispute.upheld {
+                dispute.disputer.clone()
+            } else if disputer_is_recipient {
+                escrow.depositor.clone()
+            } else {
+                escrow.recipient.clone()
+            };
+            safe_transfer(
+                &token_client,
+                &env.current_contract_address(),
+                &fee_to,
+                &dispute.fee,
+            )?;
+        }
+        dispute.resolved = true;
+        env.storage().persistent().set(&dispute_key, &dispute);
+    }
+
+    Ok(paid)
+}
+
+// Moves tokens and checks both balances shifted by exactly `amount`, so a
+// fee-on-transfer or silently failing token cannot under-deliver
+fn safe_transfer(
+    token_client: &token::Client,
+    from: &Address,
+    to: &Address,
+    amount: &i128,
+) -> Result<(), Error> {
+    let before = (token_client.balance(from), token_client.balance(to));
+    token_client.transfer(from, to, amount);
+    verify_transfer(token_client, from, to, *amount, before)
+}
+
+// Allowance-based counterpart of `safe_transfer`
+fn safe_transfer_from(
+    token_client: &token::Client,
+    spender: &Address,
+    from: &Address,
+    to: &Address,
+    amount: &i128,
+) -> Result<(), Error> {
+    let before = (token_client.balance(from), token_client.balance(to));
+    token_client.transfer_from(spender, from, to, amount);
+    verify_transfer(token_client, from, to, *amount, before)
+}
+
+fn verify_transfer(
+    token_client: &token::Client,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    (from_before, to_before): (i128, i128),
+) -> Result<(), Error> {
+    let sent = from_before.checked_sub(token_client.balance(from));
+    let received = token_client.balance(to).checked_sub(to_before);
+    if sent != Some(amount) || received != Some(amount) {
+        return Err(Error::TokenTransferFailed);
+    }
+    Ok(())
+}
+
+// Pays a confirmed delivery to the milestone's payee, net of the platform
+// fee, after the release checks shared by buyer confirmation and auto-release
+fn pay_delivery(
+    env: &Env,
+    storage_key: &(Symbol, u64),
+    escrow: &mut Escrow,
+    escrow_id: u64,
+    milestone_index: u32,
+) -> Result<(), Error> {
+    // Check escrow is active
+    if escrow.status != EscrowStatus::Active {
+        return Err(Error::EscrowNotActive);
+    }
+
+    // Vesting escrows pay out through claim_vested only
+    ensure_not_vesting(escrow)?;
+
+    // Load the milestone, rejecting an out-of-range index
+    let milestone = milestone_at(escrow, milestone_index)?;
+
+    // Check if already released
+    if is_settled(&milestone) {
+        return Err(Error::MilestoneAlreadyReleased);
+    }
+
+    // Disputed milestones are frozen until the dispute is resolved
+    if milestone.status == MilestoneStatus::Disputed {
+        return Err(Error::MilestoneDisputed);
+    }
+
+    // Ensure this escrow alone holds enough of the token to cover the release
+    ensure_escrow_balance(escrow, &escrow.token, milestone.amount)?;
+
+    // Prerequisite milestones must be released first
+    ensure_dependency_met(escrow, &milestone)?;
+
+    // Defer to the escrow's verifier for any external release conditions
+    ensure_conditions_met(env, escrow, escrow_id)?;
+
+    // Recipient-side signers must have approved the delivery
+    ensure_quorum_met(env, escrow, escrow_id, milestone_index)?;
+
+    // The recipient must have submitted a delivery proof when required
+    if escrow.require_proof
+        && !env
+            .storage()
+            .persistent()
+            .has(&get_proof_key(escrow_id, milestone_index))
+    {
+        return Err(Error::ConditionsNotMet);
+    }
+
+    // Throttle rapid state changes on contentious escrows
+    record_action(env, escrow)?;
+
+    // Space out releases so the recipient or arbiter can react
+    record_release(env, escrow)?;
+
+    // Pay the payee and the platform fee to the treasury
+    let (treasury, fee_bps) = VaultixEscrow::get_config(env.clone())?;
+    let plan = pay_out(
+        env,
+        escrow,
+        escrow_id,
+        milestone_index,
+        &treasury,
+        fee_bps,
+        true,
+    )?;
+
+    // Approvals are spent once the milestone is released
+    env.storage()
+        .persistent()
+        .remove(&get_approvals_key(escrow_id, milestone_index));
+
+    // Save updated escrow
+    env.storage().persistent().set(storage_key, escrow);
+
+    // Extend TTL
+    extend_escrow_ttl(env, escrow_id);
+
+    notify_release(env, escrow, escrow_id, milestone_index, plan.to_recipient);
+
+    // Emit event for milestone release
+    publish_event(
+        env,
+        symbol_short!("released"),
+        escrow_id,
+        (milestone_index, plan.to_recipient, plan.recipient),
+    );
+
+    Ok(())
+}
+
+// Tells the escrow's callback contract, if any, about a release. Errors from
+// the callback are swallowed so a faulty listener cannot block payouts.
+fn notify_release(env: &Env, escrow: &Escrow, escrow_id: u64, milestone_index: u32, amount: i128) {
+    if let Some(callback) = &escrow.callback {
+        let _ = ReleaseListenerClient::new(env, callback).try_on_release(
+            &escrow_id,
+            &milestone_index,
+            &amount,
+        );
+    }
+}
+
+// Ensures enough recipient-side signers approved the milestone's delivery
+fn ensure_quorum_met(
+    env: &Env,
+    escrow: &Escrow,
+    escrow_id: u64,
+    milestone_index: u32,
+) -> Result<(), Error> {
+    if escrow.recipient_threshold == 0 {
+        return Ok(());
+    }
+    let approvals: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&get_approvals_key(escrow_id, milestone_index))
+        .unwrap_or(Vec::new(env));
+    if approvals.len() < escrow.recipient_threshold {
+        return Err(Error::QuorumNotReached);
+    }
+    Ok(())
+}
+
+// Adds a release to the escrow's running total, refusing to pay out more
+// than the escrow holds in case milestone bookkeeping ever drifts
+fn add_released(escrow: &mut Escrow, amount: i128) -> Result<(), Error> {
+    let released = escrow
+        .total_released
+        .checked_add(amount)
+        .ok_or(Error::InvalidMilestoneAmount)?;
+    if released > escrow.total_amount {
+        return Err(Error::OverReleased);
+    }
+    escrow.total_released = released;
+    Ok(())
+}
+
+// Ensures a transfer of `amount` stays within this escrow's own funds
+fn ensure_escrow_balance(escrow: &Escrow, token: &Address, amount: i128) -> Result<(), Error> {
+    if amount > escrow_balance(escrow, token) {
+        return Err(Error::InsufficientBalance);
+    }
+    Ok(())
+}
+
+// Resolves who is paid when a milestone is released
+fn milestone_payee(escrow: &Escrow, milestone: &Milestone) -> Result<Address, Error> {
+    let payee = milestone
+        .recipient
+        .clone()
+        .unwrap_or(escrow.recipient.clone());
+    ensure_whitelisted(escrow, &payee)?;
+    Ok(payee)
+}
+
+// Rejects payouts to addresses outside the escrow's recipient whitelist
+fn ensure_whitelisted(escrow: &Escrow, payee: &Address) -> Result<(), Error> {
+    if !escrow.recipient_whitelist.is_empty() && !escrow.recipient_whitelist.contains(payee) {
+        return Err(Error::RecipientNotWhitelisted);
+    }
+    Ok(())
+}
+
+// Releases a milestone: pays its payee net of the platform fee, sends the
+// fee to the treasury and books the release. Callers run the release gates
+// first. `announce_fee` controls the `fee_coll` event, so batch operations on
+// quiet escrows can skip it.
+fn pay_out(
+    env: &Env,
+    escrow: &mut Escrow,
+    escrow_id: u64,
+    milestone_index: u32,
+    treasury: &Address,
+    fee_bps: i128,
+    announce_fee: bool,
+) -> Result<ReleasePlan, Error> {
+    let mut milestone = milestone_at(escrow, milestone_index)?;
+
+    // Ensure this escrow alone holds enough of the token to cover the release
+    ensure_escrow_balance(escrow, &escrow.token_address, milestone.amount)?;
+
+    // Split the milestone into recipient payout and platform fee
+    let plan = plan_release(escrow, &milestone, fee_bps)?;
+
+    // Update milestone status
+    milestone.status = MilestoneStatus::Released;
+    milestone.released_at = Some(env.ledger().timestamp());
+    escrow.milestones.set(milestone_index, milestone.clone());
+
+    // Update total released with overflow protection
+    add_released(escrow, milestone.amount)?;
+    reduce_tvl(env, milestone.amount);
+
+    let token_client = token::Client::new(env, &escrow.token_address);
+    safe_transfer(
+        &token_client,
+        &env.current_contract_address(),
+        &plan.recipient,
+        &plan.to_recipient,
+    )?;
+
+    // Transfer fee to treasury (only if fee > 0)
+    if plan.fee > 0 {
+        safe_transfer(
+            &token_client,
+            &env.current_contract_address(),
+            treasury,
+            &plan.fee,
+        )?;
+        record_fee(env, &escrow.token_address, plan.fee);
+
+        if announce_fee {
+            publish_event(
+                env,
+                symbol_short!("fee_coll"),
+                escrow_id,
+                (milestone_index, plan.fee, treasury.clone()),
+            );
+        }
+    }
+
+    Ok(plan)
+}
+
+// Computes the recipient payout and platform fee for a milestone release
+fn plan_release(
+    escrow: &Escrow,
+    milestone: &Milestone,
+    fee_bps: i128,
+) -> Result<ReleasePlan, Error> {
+    // Calculate platform fee using integer math
+    // fee = (amount * fee_bps) / 10000
+    let fee = calculate_fee(milestone.amount, fee_bps)?;
+    let to_recipient = milestone
+        .amount
+        .checked_sub(fee)
+        .ok_or(Error::InvalidMilestoneAmount)?;
+
+    Ok(ReleasePlan {
+        recipient: milestone_payee(escrow, milestone)?,
+        to_recipient,
+        fee,
+    })
+}
+
 /// Calculates platform fee using basis points with integer math.
 ///
 /// # Arguments
@@ -1,5 +1,10 @@
 use super::*;
-use soroban_sdk::{Address, Env, testutils::Address as _, token, vec};
+use soroban_sdk::{
+    Address, Env, IntoVal, String, TryFromVal, contract, contractimpl,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke, storage::Persistent as _},
+    token, vec,
+    xdr::{ContractEventBody, ScVal},
+};
 
 /// Helper function to create and initialize a test token
 /// Returns admin client for minting and the token address
@@ -10,6 +15,66 @@ fn create_test_token<'a>(env: &Env, admin: &Address) -> (token::StellarAssetClie
     (token_admin_client, token_address)
 }
 
+/// Helper function to create and fund an escrow with one milestone per amount
+/// Returns the depositor, recipient and token address
+fn setup_funded_escrow(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    escrow_id: u64,
+    amounts: &[i128],
+) -> (Address, Address, Address) {
+    let depositor = Address::generate(env);
+    let recipient = Address::generate(env);
+    let admin = Address::generate(env);
+
+    let (token_admin, token_address) = create_test_token(env, &admin);
+    let token_client = token::Client::new(env, &token_address);
+
+    let mut milestones = Vec::new(env);
+    let mut total: i128 = 0;
+    for amount in amounts {
+        milestones.push_back(Milestone {
+            amount: *amount,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        });
+        total += amount;
+    }
+    token_admin.mint(&depositor, &total);
+
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    token_client.approve(&depositor, &client.address, &total, &200);
+    client.deposit_funds(&escrow_id);
+
+    (depositor, recipient, token_address)
+}
+
+/// Helper function to count the contract's events carrying `escrow_id` as a topic
+fn count_escrow_events(env: &Env, contract_id: &Address, escrow_id: u64) -> usize {
+    env.events()
+        .all()
+        .filter_by_contract(contract_id)
+        .events()
+        .iter()
+        .filter(|event| {
+            let ContractEventBody::V0(body) = &event.body;
+            body.topics.get(2) == Some(&ScVal::U64(escrow_id))
+        })
+        .count()
+}
+
 #[test]
 fn test_create_and_get_escrow() {
     let env = Env::default();
@@ -33,16 +98,31 @@ fn test_create_and_get_escrow() {
             amount: 3000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Design"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
         Milestone {
             amount: 3000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Dev"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
         Milestone {
             amount: 4000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Deploy"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -97,11 +177,21 @@ fn test_deposit_funds() {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
         Milestone {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -138,6 +228,9 @@ fn test_release_milestone_with_tokens() {
     let contract_id = env.register(VaultixEscrow, ());
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
@@ -155,11 +248,21 @@ fn test_release_milestone_with_tokens() {
             amount: 6000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
         Milestone {
             amount: 4000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -207,6 +310,9 @@ fn test_complete_escrow_with_all_releases() {
     let contract_id = env.register(VaultixEscrow, ());
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
@@ -224,11 +330,21 @@ fn test_complete_escrow_with_all_releases() {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
         Milestone {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -287,6 +403,11 @@ fn test_cancel_escrow_with_refund() {
             amount: 10000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Work"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -307,7 +428,7 @@ fn test_cancel_escrow_with_refund() {
     assert_eq!(token_client.balance(&depositor), 0);
 
     // Cancel escrow before any releases
-    client.cancel_escrow(&escrow_id);
+    client.cancel_escrow(&escrow_id, &None);
 
     // Verify funds returned to depositor
     assert_eq!(token_client.balance(&contract_id), 0);
@@ -315,6 +436,99 @@ fn test_cancel_escrow_with_refund() {
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Cancelled);
+    assert_eq!(escrow.total_refunded, 10_000);
+    assert!(client.verify_invariants(&escrow_id));
+}
+
+#[test]
+fn test_recipient_request_funded_on_acceptance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 94u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &5000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 5000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Invoice"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    // The recipient proposes the escrow; nothing moves until it is accepted
+    client.request_escrow(
+        &escrow_id,
+        &recipient,
+        &depositor,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(env.auths()[0].0, recipient);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Created);
+    assert_eq!(escrow.depositor, depositor);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    // An unaccepted request is not listed under the depositor
+    assert!(client.escrows_of_depositor(&depositor, &0, &10).is_empty());
+
+    // Only the addressed depositor can accept
+    assert_eq!(
+        client.try_accept_request(&escrow_id, &recipient),
+        Err(Ok(Error::UnauthorizedAccess))
+    );
+
+    token_client.approve(&depositor, &contract_id, &5000, &200);
+    client.accept_request(&escrow_id, &depositor);
+    assert_eq!(client.get_escrow(&escrow_id).status, EscrowStatus::Active);
+    assert_eq!(token_client.balance(&contract_id), 5000);
+    assert_eq!(token_client.balance(&depositor), 0);
+    assert_eq!(
+        client.escrows_of_depositor(&depositor, &0, &10),
+        vec![&env, escrow_id]
+    );
+}
+
+#[test]
+fn test_remaining_milestones() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 99u64;
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[100, 200, 300]);
+    assert_eq!(client.remaining_milestones(&escrow_id).len(), 3);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+
+    let remaining = client.remaining_milestones(&escrow_id);
+    assert_eq!(remaining.len(), 2);
+    let (first_index, first) = remaining.get(0).unwrap();
+    let (second_index, second) = remaining.get(1).unwrap();
+    assert_eq!((first_index, first.amount), (1, 200));
+    assert_eq!((second_index, second.amount), (2, 300));
 }
 
 #[test]
@@ -338,6 +552,11 @@ fn test_cancel_unfunded_escrow() {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -352,12 +571,86 @@ fn test_cancel_unfunded_escrow() {
     );
 
     // Cancel unfunded escrow (no refund needed)
-    client.cancel_escrow(&escrow_id);
+    client.cancel_escrow(&escrow_id, &None);
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Cancelled);
 }
 
+#[test]
+fn test_funding_deadline_expires_unfunded_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &10_000);
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 5000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    for escrow_id in [89u64, 90u64] {
+        client.create_escrow(
+            &escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+        client.set_funding_deadline(&escrow_id, &Some(2_000));
+    }
+    assert_eq!(
+        client.try_set_funding_deadline(&89, &Some(1_000)),
+        Err(Ok(Error::InvalidDeadline))
+    );
+
+    // Funding inside the window works as usual
+    client.deposit_funds(&89);
+    assert_eq!(client.get_escrow(&89).status, EscrowStatus::Active);
+    assert_eq!(
+        client.try_expire_funding(&89),
+        Err(Ok(Error::EscrowAlreadyFunded))
+    );
+
+    // The other escrow cannot be expired until the window closes
+    assert_eq!(
+        client.try_expire_funding(&90),
+        Err(Ok(Error::ActionTooSoon))
+    );
+
+    env.ledger().set_timestamp(2_001);
+    assert_eq!(
+        client.try_deposit_funds(&90),
+        Err(Ok(Error::FundingDeadlinePassed))
+    );
+
+    // Anyone may expire it once the deadline has passed
+    client.expire_funding(&90);
+    assert_eq!(client.get_escrow(&90).status, EscrowStatus::Cancelled);
+    assert_eq!(token_client.balance(&depositor), 5000);
+    assert_eq!(client.total_value_locked(), 5000);
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #2)")]
 fn test_duplicate_escrow_id() {
@@ -380,6 +673,11 @@ fn test_duplicate_escrow_id() {
             amount: 1000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Test"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -430,6 +728,11 @@ fn test_double_release() {
             amount: 1000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -475,6 +778,11 @@ fn test_too_many_milestones() {
             amount: 100,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         });
     }
 
@@ -511,6 +819,11 @@ fn test_invalid_milestone_amount() {
             amount: 0, // Invalid: zero amount
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -546,6 +859,11 @@ fn test_zero_amount_milestone_rejected() {
             amount: 0,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Test"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -584,6 +902,11 @@ fn test_negative_amount_milestone_rejected() {
             amount: -1000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Test"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -621,6 +944,11 @@ fn test_self_dealing_rejected() {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -659,11 +987,21 @@ fn test_valid_escrow_creation_succeeds() {
             amount: 3000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
         Milestone {
             amount: 7000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -713,6 +1051,11 @@ fn test_double_deposit_rejected() {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -754,6 +1097,11 @@ fn test_release_milestone_before_deposit() {
             amount: 5000,
             status: MilestoneStatus::Pending,
             description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
         },
     ];
 
@@ -770,3 +1118,5539 @@ fn test_release_milestone_before_deposit() {
     // This should panic with Error #9 (EscrowNotActive)
     client.release_milestone(&escrow_id, &0, &token_address);
 }
+
+#[test]
+fn test_release_cannot_drain_other_escrow_funds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+
+    token_admin.mint(&depositor, &6000);
+
+    // Two escrows funded with the same token share the contract's balance
+    let small = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Small"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    let large = vec![
+        &env,
+        Milestone {
+            amount: 5000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Large"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    client.create_escrow(
+        &17u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &small,
+        &1706400000u64,
+    );
+    client.create_escrow(
+        &18u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &large,
+        &1706400000u64,
+    );
+    token_client.approve(&depositor, &contract_id, &6000, &200);
+    client.deposit_funds(&17u64);
+    client.deposit_funds(&18u64);
+    assert_eq!(token_client.balance(&contract_id), 6000);
+
+    // Simulate an accounting bug inflating the small escrow's milestone
+    env.as_contract(&contract_id, || {
+        let key = get_storage_key(17u64);
+        let mut escrow: Escrow = env.storage().persistent().get(&key).unwrap();
+        let mut milestone = escrow.milestones.get(0).unwrap();
+        milestone.amount = 3000;
+        escrow.milestones.set(0, milestone);
+        env.storage().persistent().set(&key, &escrow);
+    });
+
+    // The contract holds enough in aggregate, but the escrow itself does not
+    let result = client.try_release_milestone(&17u64, &0, &token_address);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+    let result = client.try_confirm_delivery(&17u64, &0, &depositor);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+
+    assert_eq!(token_client.balance(&contract_id), 6000);
+    assert_eq!(token_client.balance(&recipient), 0);
+}
+
+#[test]
+fn test_release_rejects_foreign_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let (other_admin, other_token) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+
+    token_admin.mint(&depositor, &1000);
+    // Another escrow's funds in a different token sit in the contract
+    other_admin.mint(&contract_id, &1000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    client.create_escrow(
+        &19u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&19u64);
+
+    // Releasing in a token this escrow never held must fail
+    let result = client.try_release_milestone(&19u64, &0, &other_token);
+    assert_eq!(result, Err(Ok(Error::InsufficientBalance)));
+    assert_eq!(
+        token::Client::new(&env, &other_token).balance(&contract_id),
+        1000
+    );
+}
+
+#[test]
+fn test_dispute_milestone_records_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 20u64;
+    let (_, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+
+    let reason = String::from_str(&env, "work not delivered");
+    client.dispute_milestone(&escrow_id, &1, &recipient, &reason);
+
+    // Dispute event carries the disputer and reason
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (
+                    symbol_short!("escrow"),
+                    symbol_short!("disputed"),
+                    escrow_id
+                )
+                    .into_val(&env),
+                (1u32, recipient.clone(), reason.clone()).into_val(&env),
+            ),
+        ]
+    );
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(
+        escrow.milestones.get(1).unwrap().status,
+        MilestoneStatus::Disputed
+    );
+
+    // Reason round-trips through storage
+    let dispute: DisputeInfo = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&get_dispute_key(escrow_id, 1))
+            .unwrap()
+    });
+    assert_eq!(dispute.disputer, recipient);
+    assert_eq!(dispute.reason, reason);
+}
+
+#[test]
+fn test_dispute_details() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 88u64;
+    let (_, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+    assert!(client.dispute_details(&escrow_id, &1).is_none());
+
+    env.ledger().set_timestamp(1_700_000_000);
+    let reason = String::from_str(&env, "late delivery");
+    client.dispute_milestone(&escrow_id, &1, &recipient, &reason);
+
+    let details = client.dispute_details(&escrow_id, &1).unwrap();
+    assert_eq!(details.disputer, recipient);
+    assert_eq!(details.reason, reason);
+    assert_eq!(details.disputed_at, 1_700_000_000);
+    assert!(!details.resolved);
+
+    // Undisputed milestones and bad indices are told apart
+    assert!(client.dispute_details(&escrow_id, &0).is_none());
+    assert_eq!(
+        client.try_dispute_details(&escrow_id, &2).err(),
+        Some(Ok(Error::MilestoneNotFound))
+    );
+}
+
+#[test]
+fn test_is_releasable_tracks_disputes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 91u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 2000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: Some(0),
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_arbiter(&escrow_id, &arbiter, &3000);
+
+    // Nothing is releasable before the escrow is funded
+    assert!(!client.is_releasable(&escrow_id, &0));
+
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+    assert!(client.is_releasable(&escrow_id, &0));
+    assert!(!client.is_releasable(&escrow_id, &1));
+    assert!(!client.is_releasable(&escrow_id, &2));
+
+    // A dispute blocks the milestone and everything depending on it
+    let reason = String::from_str(&env, "disagreement");
+    client.dispute_milestone(&escrow_id, &0, &recipient, &reason);
+    assert!(!client.is_releasable(&escrow_id, &0));
+    assert!(!client.is_releasable(&escrow_id, &1));
+
+    // Resolving it in the recipient's favour unblocks the dependent milestone
+    client.resolve_dispute(&escrow_id, &0, &true);
+    assert!(!client.is_releasable(&escrow_id, &0));
+    assert!(client.is_releasable(&escrow_id, &1));
+    client.release_milestone(&escrow_id, &1, &token_address);
+}
+
+#[test]
+fn test_pause_escrow_holds_releases() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 92u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+
+    // Only the depositor or the admin may pause
+    assert_eq!(
+        client.try_pause_escrow(&escrow_id, &recipient, &symbol_short!("audit")),
+        Err(Ok(Error::UnauthorizedAccess))
+    );
+
+    client.pause_escrow(&escrow_id, &depositor, &symbol_short!("audit"));
+    assert_eq!(
+        client.get_escrow(&escrow_id).pause_reason,
+        Some(symbol_short!("audit"))
+    );
+    assert!(!client.is_releasable(&escrow_id, &0));
+    assert_eq!(
+        client.try_release_milestone(&escrow_id, &0, &token_address),
+        Err(Ok(Error::EscrowPaused))
+    );
+
+    // Disputes stay open while releases are held
+    let reason = String::from_str(&env, "late delivery");
+    client.dispute_milestone(&escrow_id, &1, &recipient, &reason);
+
+    // The admin may lift a depositor's pause
+    client.resume_escrow(&escrow_id, &treasury);
+    assert_eq!(client.get_escrow(&escrow_id).pause_reason, None);
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&recipient),
+        1000
+    );
+}
+
+#[test]
+fn test_pause_escrow_holds_dispute_resolutions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let mut milestones = Vec::new(&env);
+    for amount in [1000i128, 2000] {
+        milestones.push_back(Milestone {
+            amount,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        });
+    }
+
+    let escrow_id = 206u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_arbiter(&escrow_id, &Address::generate(&env), &3000);
+    client.set_dispute_default(&escrow_id, &10, &DisputeDefault::Recipient);
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+
+    let reason = String::from_str(&env, "late delivery");
+    client.dispute_milestone(&escrow_id, &0, &recipient, &reason);
+    client.dispute_milestone(&escrow_id, &1, &recipient, &reason);
+    client.pause_escrow(&escrow_id, &depositor, &symbol_short!("audit"));
+
+    // Neither the arbiter nor the stale-dispute default can pay out
+    env.ledger().set_timestamp(env.ledger().timestamp() + 11);
+    assert_eq!(
+        client.try_resolve_dispute(&escrow_id, &0, &true),
+        Err(Ok(Error::EscrowPaused))
+    );
+    assert_eq!(
+        client.try_resolve_stale_dispute(&escrow_id, &1),
+        Err(Ok(Error::EscrowPaused))
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    client.resume_escrow(&escrow_id, &depositor);
+    client.resolve_dispute(&escrow_id, &0, &true);
+    client.resolve_stale_dispute(&escrow_id, &1);
+    assert_eq!(token_client.balance(&recipient), 3000);
+}
+
+#[test]
+fn test_release_all_completes_half_released_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+
+    let escrow_id = 95u64;
+    let (_, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000, 3000, 4000]);
+    let token_client = token::Client::new(&env, &token_address);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    client.release_milestone(&escrow_id, &1, &token_address);
+    client.release_all(&escrow_id);
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Completed);
+    assert_eq!(escrow.total_released, 10_000);
+    for milestone in escrow.milestones.iter() {
+        assert_eq!(milestone.status, MilestoneStatus::Released);
+    }
+
+    // Every milestone paid its 1% fee
+    assert_eq!(token_client.balance(&recipient), 9_900);
+    assert_eq!(token_client.balance(&treasury), 100);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    // A disputed milestone blocks the bulk release
+    let (_, recipient, _) = setup_funded_escrow(&env, &client, 96, &[1000, 2000]);
+    let reason = String::from_str(&env, "incomplete");
+    client.dispute_milestone(&96, &1, &recipient, &reason);
+    assert_eq!(
+        client.try_release_all(&96),
+        Err(Ok(Error::MilestoneDisputed))
+    );
+}
+
+#[test]
+fn test_quiet_escrow_emits_fewer_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+
+    setup_funded_escrow(&env, &client, 97, &[1000, 2000, 3000]);
+    setup_funded_escrow(&env, &client, 98, &[1000, 2000, 3000]);
+    client.set_quiet(&98, &true);
+
+    // Releases and fees for each of three milestones, plus completion
+    client.release_all(&97);
+    assert_eq!(count_escrow_events(&env, &contract_id, 97), 7);
+
+    // Only the completion is announced
+    client.release_all(&98);
+    assert_eq!(count_escrow_events(&env, &contract_id, 98), 1);
+    assert_eq!(client.get_escrow(&98).status, EscrowStatus::Completed);
+}
+
+#[test]
+fn test_dispute_milestone_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 21u64;
+    let (depositor, _, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    let outsider = Address::generate(&env);
+
+    let empty = String::from_str(&env, "");
+    let result = client.try_dispute_milestone(&escrow_id, &0, &depositor, &empty);
+    assert_eq!(result, Err(Ok(Error::InvalidReason)));
+
+    let too_long = String::from_str(
+        &env,
+        "this reason is far too long to be stored on chain as a dispute memo",
+    );
+    let result = client.try_dispute_milestone(&escrow_id, &0, &depositor, &too_long);
+    assert_eq!(result, Err(Ok(Error::InvalidReason)));
+
+    let reason = String::from_str(&env, "late");
+    let result = client.try_dispute_milestone(&escrow_id, &0, &outsider, &reason);
+    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+
+    client.dispute_milestone(&escrow_id, &0, &depositor, &reason);
+
+    // Disputed milestones cannot be disputed again or released
+    let result = client.try_dispute_milestone(&escrow_id, &0, &depositor, &reason);
+    assert_eq!(result, Err(Ok(Error::MilestoneDisputed)));
+    let result = client.try_confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(result, Err(Ok(Error::MilestoneDisputed)));
+}
+
+#[test]
+fn test_config_defaults_before_initialize() {
+    let env = Env::default();
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let config = client.config();
+    assert_eq!(config.admin, None);
+    assert!(!config.paused);
+    assert_eq!(config.max_milestones, 20);
+    assert_eq!(config.fee_bps, 50);
+    assert_eq!(config.fee_collector, None);
+}
+
+#[test]
+fn test_config_reflects_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+    client.set_max_milestones(&5);
+    client.set_paused(&true);
+
+    let config = client.config();
+    assert_eq!(config.admin, Some(treasury.clone()));
+    assert!(config.paused);
+    assert_eq!(config.max_milestones, 5);
+    assert_eq!(config.fee_bps, 100);
+    assert_eq!(config.fee_collector, Some(treasury));
+
+    let result = client.try_set_max_milestones(&0);
+    assert_eq!(result, Err(Ok(Error::InvalidMilestoneLimit)));
+}
+
+#[test]
+fn test_max_milestones_and_pause_are_enforced() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+    client.set_max_milestones(&1);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (_, token_address) = create_test_token(&env, &admin);
+
+    let milestone = Milestone {
+        amount: 100,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Task"),
+        depends_on: None,
+        released_at: None,
+        recipient: None,
+        deadline: None,
+        metadata_uri: None,
+    };
+    let milestones = vec![&env, milestone.clone(), milestone.clone()];
+    let result = client.try_create_escrow(
+        &22u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+
+    client.set_paused(&true);
+    let milestones = vec![&env, milestone];
+    let result = client.try_create_escrow(
+        &22u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    client.set_paused(&false);
+    client.create_escrow(
+        &22u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+}
+
+#[test]
+fn test_arbiter_resolution_within_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 23u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 2000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_arbiter(&escrow_id, &arbiter, &3000);
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+
+    let reason = String::from_str(&env, "disagreement");
+    client.dispute_milestone(&escrow_id, &0, &recipient, &reason);
+    client.dispute_milestone(&escrow_id, &1, &depositor, &reason);
+
+    // Arbiter releases one milestone and refunds the other, exactly hitting the cap
+    client.resolve_dispute(&escrow_id, &0, &true);
+    client.resolve_dispute(&escrow_id, &1, &false);
+
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert_eq!(token_client.balance(&depositor), 2000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.arbiter_moved, 3000);
+    assert_eq!(escrow.total_released, 1000);
+    assert_eq!(escrow.total_refunded, 2000);
+    assert_eq!(
+        escrow.milestones.get(1).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+
+    // Settled milestones allow completion
+    client.complete_escrow(&escrow_id);
+    assert_eq!(client.get_state(&escrow_id), EscrowStatus::Completed);
+}
+
+#[test]
+fn test_arbiter_cap_exceeded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 24u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 2000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_arbiter(&escrow_id, &arbiter, &1500);
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Arbiter cannot be reassigned after funding
+    let result = client.try_set_arbiter(&escrow_id, &depositor, &3000);
+    assert_eq!(result, Err(Ok(Error::EscrowAlreadyFunded)));
+
+    let reason = String::from_str(&env, "disagreement");
+    client.dispute_milestone(&escrow_id, &0, &recipient, &reason);
+    client.dispute_milestone(&escrow_id, &1, &recipient, &reason);
+
+    client.resolve_dispute(&escrow_id, &0, &true);
+
+    // A further 2000 would push the arbiter past its 1500 cap
+    let result = client.try_resolve_dispute(&escrow_id, &1, &true);
+    assert_eq!(result, Err(Ok(Error::ArbiterCapExceeded)));
+    assert_eq!(token_client.balance(&contract_id), 2000);
+
+    // Only disputed milestones can be resolved
+    let result = client.try_resolve_dispute(&escrow_id, &0, &true);
+    assert_eq!(result, Err(Ok(Error::MilestoneNotDisputed)));
+}
+
+#[test]
+fn test_settle_escrow_releases_and_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    // Default 0.5% platform fee
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let escrow_id = 25u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000, 3000]);
+    let token_client = token::Client::new(&env, &token_address);
+
+    // Release the middle milestone, refund the other two
+    client.settle_escrow(&escrow_id, &vec![&env, 1u32]);
+
+    // The settlement event reports what each party actually received
+    let events = env.events().all().filter_by_contract(&contract_id);
+    let ContractEventBody::V0(body) = &events.events().last().unwrap().body;
+    let expected: Val = (1990i128, 4000i128).into_val(&env);
+    assert_eq!(body.data, ScVal::try_from_val(&env, &expected).unwrap());
+
+    // The released milestone pays the platform fee, refunds do not
+    assert_eq!(token_client.balance(&recipient), 1990);
+    assert_eq!(token_client.balance(&treasury), 10);
+    assert_eq!(client.total_fees_collected(&token_address), 10);
+    assert_eq!(token_client.balance(&depositor), 4000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Completed);
+    assert_eq!(escrow.total_released, 2000);
+    assert_eq!(escrow.total_refunded, 4000);
+    assert_eq!(
+        escrow.milestones.get(0).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+    assert_eq!(
+        escrow.milestones.get(1).unwrap().status,
+        MilestoneStatus::Released
+    );
+    assert_eq!(
+        escrow.milestones.get(2).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+}
+
+#[test]
+fn test_settle_escrow_rejects_released_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 26u64;
+    let (depositor, _, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+
+    let result = client.try_settle_escrow(&escrow_id, &vec![&env, 0u32]);
+    assert_eq!(result, Err(Ok(Error::MilestoneAlreadyReleased)));
+    let result = client.try_settle_escrow(&escrow_id, &vec![&env, 5u32]);
+    assert_eq!(result, Err(Ok(Error::MilestoneNotFound)));
+}
+
+#[test]
+fn test_events_filterable_by_escrow_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let (depositor, recipient, _) = setup_funded_escrow(&env, &client, 27u64, &[1000, 2000]);
+    setup_funded_escrow(&env, &client, 28u64, &[500]);
+
+    client.confirm_delivery(&27u64, &0, &depositor);
+
+    // The release is only visible under its own escrow's topic
+    assert_eq!(count_escrow_events(&env, &contract_id, 27u64), 1);
+    assert_eq!(count_escrow_events(&env, &contract_id, 28u64), 0);
+
+    let event = env.events().all().filter_by_contract(&contract_id);
+    assert_eq!(
+        event,
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("escrow"), symbol_short!("released"), 27u64).into_val(&env),
+                (0u32, 1000i128, recipient).into_val(&env),
+            ),
+        ]
+    );
+
+    client.cancel_escrow(&28u64, &None);
+    assert_eq!(count_escrow_events(&env, &contract_id, 28u64), 1);
+}
+
+#[test]
+fn test_escrow_age_advances_with_ledger_time() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 29u64;
+    setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    assert_eq!(client.escrow_age(&escrow_id), 0);
+
+    env.ledger().set_timestamp(4_600);
+    assert_eq!(client.escrow_age(&escrow_id), 3_600);
+
+    let result = client.try_escrow_age(&99u64);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_migrate_legacy_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (_, token_address) = create_test_token(&env, &admin);
+    let escrow_id = 30u64;
+
+    // Store an escrow in the pre-upgrade layout
+    let legacy = EscrowV1 {
+        depositor: depositor.clone(),
+        recipient: recipient.clone(),
+        token_address: token_address.clone(),
+        total_amount: 5000,
+        total_released: 0,
+        milestones: vec![
+            &env,
+            MilestoneV1 {
+                amount: 5000,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Task"),
+            },
+        ],
+        token: token_address.clone(),
+        status: EscrowStatus::Created,
+        deadline: 1706400000u64,
+    };
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&get_storage_key(escrow_id), &legacy);
+    });
+
+    client.migrate_escrow(&escrow_id);
+
+    // A second migration is refused rather than misreading the new layout
+    assert_eq!(
+        client.try_migrate_escrow(&escrow_id),
+        Err(Ok(Error::AlreadyMigrated))
+    );
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.depositor, depositor);
+    assert_eq!(escrow.recipient, recipient);
+    assert_eq!(escrow.total_amount, 5000);
+    assert_eq!(escrow.total_refunded, 0);
+    assert_eq!(escrow.arbiter, None);
+    assert_eq!(escrow.status, EscrowStatus::Created);
+
+    // The migrated escrow behaves like any other
+    client.cancel_escrow(&escrow_id, &None);
+    assert_eq!(client.get_state(&escrow_id), EscrowStatus::Cancelled);
+
+    let result = client.try_migrate_escrow(&99u64);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_private_escrow_readable_only_by_participants() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 31u64;
+    let (depositor, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    let outsider = Address::generate(&env);
+
+    client.set_private(&escrow_id, &true);
+
+    let result = client.try_get_escrow(&escrow_id);
+    assert_eq!(result.err(), Some(Ok(Error::UnauthorizedAccess)));
+    let result = client.try_get_escrow_as(&escrow_id, &outsider);
+    assert_eq!(result.err(), Some(Ok(Error::UnauthorizedAccess)));
+
+    assert_eq!(
+        client.get_escrow_as(&escrow_id, &depositor).total_amount,
+        1000
+    );
+    assert_eq!(
+        client.get_escrow_as(&escrow_id, &recipient).total_amount,
+        1000
+    );
+
+    // Derived reads are gated the same way
+    client.add_note(&escrow_id, &depositor, &String::from_str(&env, "kickoff"));
+    let private = Some(Ok(Error::UnauthorizedAccess));
+    assert_eq!(client.try_get_state(&escrow_id).err(), private);
+    assert_eq!(client.try_party_addresses(&escrow_id).err(), private);
+    assert_eq!(client.try_release_history(&escrow_id).err(), private);
+    assert_eq!(
+        client.try_milestone_description(&escrow_id, &0).err(),
+        private
+    );
+    assert_eq!(client.try_simulate_release(&escrow_id, &0).err(), private);
+    assert_eq!(client.try_dispute_details(&escrow_id, &0).err(), private);
+    assert_eq!(client.try_dispute_snapshot(&escrow_id, &0).err(), private);
+    assert_eq!(client.try_role_of(&escrow_id, &depositor).err(), private);
+    assert_eq!(
+        client.try_refundable_amount(&escrow_id, &depositor).err(),
+        private
+    );
+    assert!(client.get_notes(&escrow_id).is_empty());
+    assert!(client.recent_events(&escrow_id).is_empty());
+    assert!(!client.is_releasable(&escrow_id, &0));
+
+    // Public escrows stay readable by anyone
+    client.set_private(&escrow_id, &false);
+    assert_eq!(
+        client.get_escrow_as(&escrow_id, &outsider).total_amount,
+        1000
+    );
+    assert_eq!(client.get_escrow(&escrow_id).total_amount, 1000);
+    assert_eq!(client.get_notes(&escrow_id).len(), 1);
+}
+
+#[contract]
+struct ApprovingVerifier;
+
+#[contractimpl]
+impl ApprovingVerifier {
+    pub fn check(_env: Env, _escrow_id: u64) -> bool {
+        true
+    }
+}
+
+#[contract]
+struct RejectingVerifier;
+
+#[contractimpl]
+impl RejectingVerifier {
+    pub fn check(_env: Env, _escrow_id: u64) -> bool {
+        false
+    }
+}
+
+#[contract]
+struct RecordingListener;
+
+#[contractimpl]
+impl RecordingListener {
+    pub fn on_release(env: Env, escrow_id: u64, milestone_index: u32, amount: i128) {
+        env.storage().instance().set(
+            &symbol_short!("last"),
+            &(escrow_id, milestone_index, amount),
+        );
+    }
+
+    pub fn last(env: Env) -> Option<(u64, u32, i128)> {
+        env.storage().instance().get(&symbol_short!("last"))
+    }
+}
+
+#[contract]
+struct FailingListener;
+
+#[contractimpl]
+impl FailingListener {
+    pub fn on_release(_env: Env, _escrow_id: u64, _milestone_index: u32, _amount: i128) {
+        panic!("listener unavailable");
+    }
+}
+
+/// Helper function to create and fund a single-milestone escrow guarded by a verifier
+/// Returns the depositor, recipient and token address
+fn setup_verified_escrow(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    escrow_id: u64,
+    verifier: &Address,
+) -> (Address, Address, Address) {
+    let depositor = Address::generate(env);
+    let recipient = Address::generate(env);
+    let admin = Address::generate(env);
+
+    let (token_admin, token_address) = create_test_token(env, &admin);
+    let token_client = token::Client::new(env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestones = vec![
+        env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_verifier(&escrow_id, &Some(verifier.clone()));
+    token_client.approve(&depositor, &client.address, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    (depositor, recipient, token_address)
+}
+
+#[test]
+fn test_release_notifies_callback() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+    let listener = env.register(RecordingListener, ());
+    let failing = env.register(FailingListener, ());
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &2000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    for (escrow_id, callback) in [(107u64, &listener), (108u64, &failing)] {
+        client.create_escrow(
+            &escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+        client.set_callback(&escrow_id, &Some(callback.clone()));
+        token_client.approve(&depositor, &contract_id, &1000, &200);
+        client.deposit_funds(&escrow_id);
+    }
+
+    // The listener hears about the release, net of the platform fee
+    client.release_milestone(&107, &0, &token_address);
+    assert_eq!(
+        RecordingListenerClient::new(&env, &listener).last(),
+        Some((107, 0, 990))
+    );
+
+    // A failing listener does not hold up the payout
+    client.release_milestone(&108, &0, &token_address);
+    assert_eq!(token_client.balance(&recipient), 1980);
+
+    // A dispute resolved in the recipient's favour reports the same net amount
+    token_admin.mint(&depositor, &1000);
+    client.create_escrow(
+        &204,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_callback(&204, &Some(listener.clone()));
+    client.set_arbiter(&204, &Address::generate(&env), &1000);
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&204);
+    client.dispute_milestone(&204, &0, &depositor, &String::from_str(&env, "late"));
+    client.resolve_dispute(&204, &0, &true);
+    assert_eq!(
+        RecordingListenerClient::new(&env, &listener).last(),
+        Some((204, 0, 990))
+    );
+    assert_eq!(token_client.balance(&recipient), 2970);
+}
+
+#[test]
+fn test_release_with_approving_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+    let verifier = env.register(ApprovingVerifier, ());
+
+    let escrow_id = 32u64;
+    let (depositor, recipient, token_address) =
+        setup_verified_escrow(&env, &client, escrow_id, &verifier);
+
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&recipient),
+        1000
+    );
+}
+
+#[test]
+fn test_release_with_rejecting_verifier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+    let verifier = env.register(RejectingVerifier, ());
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let escrow_id = 33u64;
+    let (depositor, recipient, token_address) =
+        setup_verified_escrow(&env, &client, escrow_id, &verifier);
+
+    let result = client.try_confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(result, Err(Ok(Error::ConditionsNotMet)));
+    let result = client.try_release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(result, Err(Ok(Error::ConditionsNotMet)));
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&recipient),
+        0
+    );
+}
+
+#[test]
+fn test_cancel_escrow_refunds_custom_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 34u64;
+    let (depositor, _, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    let token_client = token::Client::new(&env, &token_address);
+    let refund_to = Address::generate(&env);
+
+    client.cancel_escrow(&escrow_id, &Some(refund_to.clone()));
+
+    assert_eq!(token_client.balance(&refund_to), 1000);
+    assert_eq!(token_client.balance(&depositor), 0);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(client.get_state(&escrow_id), EscrowStatus::Cancelled);
+}
+
+#[test]
+fn test_milestone_dependencies_enforce_release_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 35u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    // Deploy depends on Build, which depends on Design
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Design"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Build"),
+            depends_on: Some(0),
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Deploy"),
+            depends_on: Some(1),
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Out of order releases are rejected
+    let result = client.try_confirm_delivery(&escrow_id, &2, &depositor);
+    assert_eq!(result, Err(Ok(Error::DependencyNotMet)));
+    let result = client.try_confirm_delivery(&escrow_id, &1, &depositor);
+    assert_eq!(result, Err(Ok(Error::DependencyNotMet)));
+
+    // Releasing in dependency order succeeds
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    client.confirm_delivery(&escrow_id, &1, &depositor);
+    client.confirm_delivery(&escrow_id, &2, &depositor);
+    assert_eq!(token_client.balance(&recipient), 3000);
+}
+
+#[test]
+fn test_forward_dependency_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (_, token_address) = create_test_token(&env, &admin);
+
+    // A milestone depending on itself would never be releasable
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: Some(0),
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    let result = client.try_create_escrow(
+        &36u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidDependency)));
+}
+
+#[test]
+fn test_frozen_escrow_rejects_mutations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let escrow_id = 37u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+    let token_client = token::Client::new(&env, &token_address);
+
+    // Participants cannot freeze the escrow themselves
+    let result = client.try_freeze_escrow(&escrow_id, &recipient);
+    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+
+    client.freeze_escrow(&escrow_id, &treasury);
+    assert!(client.get_escrow(&escrow_id).frozen);
+
+    let result = client.try_confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(result, Err(Ok(Error::EscrowFrozen)));
+    let result = client.try_cancel_escrow(&escrow_id, &None);
+    assert_eq!(result, Err(Ok(Error::EscrowFrozen)));
+    let reason = String::from_str(&env, "late");
+    let result = client.try_dispute_milestone(&escrow_id, &0, &recipient, &reason);
+    assert_eq!(result, Err(Ok(Error::EscrowFrozen)));
+
+    // Unfreezing restores normal behaviour
+    client.unfreeze_escrow(&escrow_id, &treasury);
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(token_client.balance(&recipient), 995);
+}
+
+#[test]
+fn test_recent_events_track_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 38u64;
+    let (depositor, _, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    client.complete_escrow(&escrow_id);
+
+    let events = client.recent_events(&escrow_id);
+    let actions = vec![
+        &env,
+        symbol_short!("created"),
+        symbol_short!("funded"),
+        symbol_short!("released"),
+        symbol_short!("completed"),
+    ];
+    assert_eq!(events.len(), actions.len());
+    for (i, event) in events.iter().enumerate() {
+        assert_eq!(event.sequence, i as u32);
+        assert_eq!(event.action, actions.get(i as u32).unwrap());
+    }
+
+    assert_eq!(client.recent_events(&99u64).len(), 0);
+}
+
+#[test]
+fn test_recent_events_are_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let escrow_id = 39u64;
+    setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+
+    // Two events from setup plus twenty from freezing and unfreezing
+    for _ in 0..10 {
+        client.freeze_escrow(&escrow_id, &treasury);
+        client.unfreeze_escrow(&escrow_id, &treasury);
+    }
+
+    let events = client.recent_events(&escrow_id);
+    assert_eq!(events.len(), 16);
+    assert_eq!(events.first().unwrap().sequence, 6);
+    assert_eq!(events.last().unwrap().sequence, 21);
+    assert_eq!(events.last().unwrap().action, symbol_short!("unfrozen"));
+}
+
+#[test]
+fn test_simulate_release_matches_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(250));
+
+    let escrow_id = 40u64;
+    let (_, recipient, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[10_000]);
+    let token_client = token::Client::new(&env, &token_address);
+
+    let plan = client.simulate_release(&escrow_id, &0);
+    assert_eq!(plan.recipient, recipient);
+    assert_eq!(plan.fee, 250);
+    assert_eq!(plan.to_recipient, 9_750);
+
+    // Simulation moves nothing
+    assert_eq!(token_client.balance(&contract_id), 10_000);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(token_client.balance(&recipient), plan.to_recipient);
+    assert_eq!(token_client.balance(&treasury), plan.fee);
+
+    let result = client.try_simulate_release(&escrow_id, &1);
+    assert_eq!(result, Err(Ok(Error::MilestoneNotFound)));
+}
+
+#[test]
+fn test_min_action_interval_throttles_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 41u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &2000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_min_action_interval(&escrow_id, &10);
+    token_client.approve(&depositor, &contract_id, &2000, &1000);
+    client.deposit_funds(&escrow_id);
+
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+
+    // A second action in the same window is rejected
+    env.ledger().set_sequence_number(105);
+    let result = client.try_confirm_delivery(&escrow_id, &1, &depositor);
+    assert_eq!(result, Err(Ok(Error::ActionTooSoon)));
+    let reason = String::from_str(&env, "late");
+    let result = client.try_dispute_milestone(&escrow_id, &1, &recipient, &reason);
+    assert_eq!(result, Err(Ok(Error::ActionTooSoon)));
+
+    // Succeeds once the interval has elapsed
+    env.ledger().set_sequence_number(110);
+    client.confirm_delivery(&escrow_id, &1, &depositor);
+    assert_eq!(token_client.balance(&recipient), 2000);
+}
+
+#[test]
+fn test_milestone_description() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 42u64;
+    let (_, token_address) = create_test_token(&env, &admin);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Design"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 2000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Deploy"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    assert_eq!(
+        client.milestone_description(&escrow_id, &0),
+        symbol_short!("Design")
+    );
+    assert_eq!(
+        client.milestone_description(&escrow_id, &1),
+        symbol_short!("Deploy")
+    );
+
+    let result = client.try_milestone_description(&escrow_id, &2);
+    assert_eq!(result, Err(Ok(Error::MilestoneNotFound)));
+}
+
+#[test]
+fn test_token_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (_, stablecoin) = create_test_token(&env, &admin);
+    let (_, unlisted) = create_test_token(&env, &admin);
+
+    client.add_allowed_token(&stablecoin);
+    assert_eq!(client.allowed_tokens(), vec![&env, stablecoin.clone()]);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    let result = client.try_create_escrow(
+        &43u64,
+        &depositor,
+        &recipient,
+        &unlisted,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::TokenNotAllowed)));
+
+    client.create_escrow(
+        &43u64,
+        &depositor,
+        &recipient,
+        &stablecoin,
+        &milestones,
+        &1706400000u64,
+    );
+
+    // Emptying the allowlist accepts any token again
+    client.remove_allowed_token(&stablecoin);
+    client.create_escrow(
+        &44u64,
+        &depositor,
+        &recipient,
+        &unlisted,
+        &milestones,
+        &1706400000u64,
+    );
+}
+
+/// Helper function to create and fund a single-milestone escrow whose recipient posts a bond
+/// Returns the depositor, recipient and token address
+fn setup_bonded_escrow(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    escrow_id: u64,
+    amount: i128,
+    bond: i128,
+) -> (Address, Address, Address) {
+    let depositor = Address::generate(env);
+    let recipient = Address::generate(env);
+    let admin = Address::generate(env);
+
+    let (token_admin, token_address) = create_test_token(env, &admin);
+    let token_client = token::Client::new(env, &token_address);
+    token_admin.mint(&depositor, &amount);
+    token_admin.mint(&recipient, &bond);
+
+    let milestones = vec![
+        env,
+        Milestone {
+            amount,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_recipient_bond(&escrow_id, &bond);
+    token_client.approve(&depositor, &client.address, &amount, &200);
+    client.deposit_funds(&escrow_id);
+    client.post_bond(&escrow_id);
+
+    (depositor, recipient, token_address)
+}
+
+#[test]
+fn test_recipient_cancel_forfeits_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 45u64;
+    let (depositor, recipient, token_address) =
+        setup_bonded_escrow(&env, &client, escrow_id, 1000, 200);
+    let token_client = token::Client::new(&env, &token_address);
+
+    assert_eq!(token_client.balance(&contract_id), 1200);
+    assert_eq!(
+        client.get_escrow(&escrow_id).bond_status,
+        BondStatus::Posted
+    );
+
+    let result = client.try_post_bond(&escrow_id);
+    assert_eq!(result, Err(Ok(Error::BondAlreadyPosted)));
+
+    client.recipient_cancel(&escrow_id);
+
+    // Depositor gets their funds back plus the recipient's bond
+    assert_eq!(token_client.balance(&depositor), 1200);
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Cancelled);
+    assert_eq!(escrow.bond_status, BondStatus::Forfeited);
+}
+
+#[test]
+fn test_depositor_cancel_returns_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 46u64;
+    let (depositor, recipient, token_address) =
+        setup_bonded_escrow(&env, &client, escrow_id, 1000, 200);
+    let token_client = token::Client::new(&env, &token_address);
+
+    client.cancel_escrow(&escrow_id, &None);
+
+    assert_eq!(token_client.balance(&depositor), 1000);
+    assert_eq!(token_client.balance(&recipient), 200);
+    assert_eq!(
+        client.get_escrow(&escrow_id).bond_status,
+        BondStatus::Returned
+    );
+}
+
+#[test]
+fn test_completion_returns_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 135u64;
+    let (_, recipient, token_address) = setup_bonded_escrow(&env, &client, escrow_id, 1000, 200);
+    let token_client = token::Client::new(&env, &token_address);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(token_client.balance(&recipient), 1000);
+
+    client.complete_escrow(&escrow_id);
+    assert_eq!(token_client.balance(&recipient), 1200);
+    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(
+        client.get_escrow(&escrow_id).bond_status,
+        BondStatus::Returned
+    );
+
+    // Completing through release_all returns the bond too
+    let (_, recipient, token_address) = setup_bonded_escrow(&env, &client, 136, 1000, 200);
+    let token_client = token::Client::new(&env, &token_address);
+    client.release_all(&136);
+    assert_eq!(token_client.balance(&recipient), 1200);
+    assert_eq!(client.get_escrow(&136).bond_status, BondStatus::Returned);
+}
+
+#[test]
+fn test_release_history_records_timestamps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 47u64;
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[300, 300, 400]);
+
+    env.ledger().set_timestamp(1_000);
+    client.release_milestone(&escrow_id, &0, &token_address);
+
+    env.ledger().set_timestamp(5_000);
+    client.release_milestone(&escrow_id, &2, &token_address);
+
+    let history = client.release_history(&escrow_id);
+    assert_eq!(history, vec![&env, (0u32, 1_000u64), (2u32, 5_000u64)]);
+}
+
+#[test]
+fn test_create_escrow_total_overflow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_address = Address::generate(&env);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: i128::MAX - 1,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: i128::MAX - 1,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    let result = client.try_create_escrow(
+        &48u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::TotalAmountOverflow)));
+}
+
+#[test]
+fn test_party_addresses_in_creation_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 49u64;
+    let (depositor, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[500]);
+
+    assert_eq!(
+        client.party_addresses(&escrow_id),
+        vec![&env, depositor.clone(), recipient.clone()]
+    );
+
+    let result = client.try_party_addresses(&999u64);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_escrows_by_category() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    let freelance = symbol_short!("freelance");
+    let rental = symbol_short!("rental");
+    for (escrow_id, category) in [(50u64, &freelance), (51u64, &rental), (52u64, &freelance)] {
+        client.create_escrow(
+            &escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+        client.set_category(&escrow_id, category);
+    }
+
+    assert_eq!(
+        client.escrows_by_category(&freelance, &0, &10),
+        vec![&env, 50u64, 52u64]
+    );
+    assert_eq!(
+        client.escrows_by_category(&rental, &0, &10),
+        vec![&env, 51u64]
+    );
+    assert_eq!(
+        client.escrows_by_category(&freelance, &1, &10),
+        vec![&env, 52u64]
+    );
+    assert_eq!(
+        client.escrows_by_category(&symbol_short!("general"), &0, &10),
+        vec![&env]
+    );
+}
+
+#[test]
+fn test_index_write_size_is_constant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    // Every escrow lands in the same category, token and depositor indexes
+    let mut write_bytes = Vec::new(&env);
+    for escrow_id in 164u64..204 {
+        client.create_escrow(
+            &escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+        write_bytes.push_back(env.cost_estimate().resources().write_bytes);
+    }
+
+    assert_eq!(write_bytes.get(1), write_bytes.last());
+    assert_eq!(
+        client.escrows_by_category(&symbol_short!("general"), &38, &10),
+        vec![&env, 202u64, 203u64]
+    );
+    assert_eq!(
+        client.escrows_by_token(&token_address, &39, &10),
+        vec![&env, 203u64]
+    );
+    assert_eq!(
+        client.escrows_of_depositor(&depositor, &39, &10),
+        vec![&env, 203u64]
+    );
+}
+
+#[contract]
+struct RejectingToken;
+
+#[contractimpl]
+impl RejectingToken {
+    pub fn transfer_from(
+        _env: Env,
+        _spender: Address,
+        _from: Address,
+        _to: Address,
+        _amount: i128,
+    ) {
+        panic!("transfer rejected");
+    }
+}
+
+#[test]
+fn test_failed_deposit_leaves_escrow_unfunded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+    let token_address = env.register(RejectingToken, ());
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    let escrow_id = 53u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    let result = client.try_deposit_funds(&escrow_id);
+    assert!(result.is_err());
+
+    // The failed transfer rolls back, so the escrow is still waiting for funds
+    assert_eq!(client.get_state(&escrow_id), EscrowStatus::Created);
+    assert!(
+        client
+            .recent_events(&escrow_id)
+            .iter()
+            .all(|e| e.action != symbol_short!("funded"))
+    );
+}
+
+#[test]
+fn test_confirm_delivery_requires_recipient_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    let escrow_id = 54u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    let signer_a = Address::generate(&env);
+    let signer_b = Address::generate(&env);
+    let signer_c = Address::generate(&env);
+    let signers = vec![&env, signer_a.clone(), signer_b.clone(), signer_c.clone()];
+
+    let result = client.try_set_recipient_signers(&escrow_id, &signers, &4);
+    assert_eq!(result, Err(Ok(Error::InvalidThreshold)));
+    client.set_recipient_signers(&escrow_id, &signers, &2);
+
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Outsiders cannot approve on the recipient's behalf
+    let outsider = Address::generate(&env);
+    let result = client.try_approve_delivery(&escrow_id, &0, &outsider);
+    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+
+    // One approval, even given twice, is short of the 2-of-3 quorum
+    client.approve_delivery(&escrow_id, &0, &signer_a);
+    client.approve_delivery(&escrow_id, &0, &signer_a);
+    let result = client.try_confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(result, Err(Ok(Error::QuorumNotReached)));
+
+    client.approve_delivery(&escrow_id, &0, &signer_c);
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_storage_footprint_grows_with_milestones() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    setup_funded_escrow(&env, &client, 55u64, &[100]);
+    setup_funded_escrow(&env, &client, 56u64, &[100, 200, 300, 400, 500]);
+
+    let small = client.storage_footprint(&55u64);
+    let large = client.storage_footprint(&56u64);
+    assert!(small > 0);
+    assert!(large > small);
+
+    let result = client.try_storage_footprint(&999u64);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_dispute_fee_refunded_or_forfeited() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+    client.set_dispute_fee(&50);
+    assert_eq!(client.config().dispute_fee, 50);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 57u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+    token_admin.mint(&recipient, &100);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 2000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_arbiter(&escrow_id, &arbiter, &3000);
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Each dispute pulls the fee from the disputer
+    let reason = String::from_str(&env, "late delivery");
+    client.dispute_milestone(&escrow_id, &0, &recipient, &reason);
+    client.dispute_milestone(&escrow_id, &1, &recipient, &reason);
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(token_client.balance(&contract_id), 3100);
+
+    // Favorable resolution returns the fee with the milestone payout
+    client.resolve_dispute(&escrow_id, &0, &true);
+    assert_eq!(token_client.balance(&recipient), 1050);
+
+    // Unfavorable resolution forfeits the fee to the depositor
+    client.resolve_dispute(&escrow_id, &1, &false);
+    assert_eq!(token_client.balance(&recipient), 1050);
+    assert_eq!(token_client.balance(&depositor), 2050);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let dispute: DisputeInfo = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&get_dispute_key(escrow_id, 1))
+            .unwrap()
+    });
+    assert_eq!(dispute.fee, 50);
+    assert!(dispute.resolved);
+    assert!(!dispute.upheld);
+}
+
+#[test]
+fn test_set_dispute_fee_rejects_negative() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let result = client.try_set_dispute_fee(&-1);
+    assert_eq!(result, Err(Ok(Error::InvalidDisputeFee)));
+}
+
+#[test]
+fn test_refundable_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 58u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[400, 600]);
+
+    // Before any release the whole deposit can be reclaimed, but only by the depositor
+    assert_eq!(client.refundable_amount(&escrow_id, &depositor), 1000);
+    assert_eq!(client.refundable_amount(&escrow_id, &recipient), 0);
+
+    // Once a milestone is released the escrow can no longer be cancelled
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(client.refundable_amount(&escrow_id, &depositor), 0);
+}
+
+#[test]
+fn test_get_escrows_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    setup_funded_escrow(&env, &client, 59u64, &[100]);
+    setup_funded_escrow(&env, &client, 60u64, &[200, 300]);
+
+    let escrows = client.get_escrows(&vec![&env, 59u64, 999u64, 60u64]);
+    assert_eq!(escrows.len(), 3);
+    assert_eq!(escrows.get(0).unwrap().unwrap().total_amount, 100);
+    assert!(escrows.get(1).unwrap().is_none());
+    assert_eq!(escrows.get(2).unwrap().unwrap().total_amount, 500);
+
+    let mut too_many = vec![&env];
+    for escrow_id in 0..51u64 {
+        too_many.push_back(escrow_id);
+    }
+    let result = client.try_get_escrows(&too_many);
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+}
+
+#[test]
+fn test_release_cooldown_spaces_releases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 61u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &2000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_release_cooldown(&escrow_id, &5);
+    token_client.approve(&depositor, &contract_id, &2000, &1000);
+    client.deposit_funds(&escrow_id);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+
+    // A second release inside the cooldown window is rejected
+    env.ledger().set_sequence_number(104);
+    let result = client.try_confirm_delivery(&escrow_id, &1, &depositor);
+    assert_eq!(result, Err(Ok(Error::ActionTooSoon)));
+
+    env.ledger().set_sequence_number(105);
+    client.confirm_delivery(&escrow_id, &1, &depositor);
+    assert_eq!(token_client.balance(&recipient), 2000);
+}
+
+#[test]
+fn test_fee_rounding_leaves_no_dust() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(50));
+
+    // 0.5% of 333 is 1.665, which floors to a fee of 1
+    let escrow_id = 62u64;
+    let (_, recipient, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[333]);
+    client.release_milestone(&escrow_id, &0, &token_address);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&treasury), 1);
+    assert_eq!(token_client.balance(&recipient), 332);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_milestone_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    setup_funded_escrow(&env, &client, 63u64, &[100, 200, 300]);
+    assert_eq!(client.milestone_count(&63u64), 3);
+
+    let result = client.try_milestone_count(&999u64);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_notes_from_both_parties() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 64u64;
+    let (depositor, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[500]);
+
+    let first = String::from_str(&env, "Please send the draft by Friday");
+    let second = String::from_str(&env, "Draft uploaded");
+    env.ledger().set_timestamp(1_000);
+    client.add_note(&escrow_id, &depositor, &first);
+    env.ledger().set_timestamp(2_000);
+    client.add_note(&escrow_id, &recipient, &second);
+
+    assert_eq!(
+        client.get_notes(&escrow_id),
+        vec![
+            &env,
+            (depositor.clone(), 1_000u64, first),
+            (recipient.clone(), 2_000u64, second),
+        ]
+    );
+
+    let outsider = Address::generate(&env);
+    let result = client.try_add_note(&escrow_id, &outsider, &String::from_str(&env, "hi"));
+    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+
+    let result = client.try_add_note(&escrow_id, &depositor, &String::from_str(&env, ""));
+    assert_eq!(result, Err(Ok(Error::InvalidNote)));
+}
+
+#[test]
+fn test_notes_are_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 65u64;
+    let (depositor, _, _) = setup_funded_escrow(&env, &client, escrow_id, &[500]);
+
+    let note = String::from_str(&env, "status update");
+    for _ in 0..20 {
+        client.add_note(&escrow_id, &depositor, &note);
+    }
+
+    let result = client.try_add_note(&escrow_id, &depositor, &note);
+    assert_eq!(result, Err(Ok(Error::TooManyNotes)));
+    assert_eq!(client.get_notes(&escrow_id).len(), 20);
+}
+
+#[test]
+fn test_two_step_admin_handover() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    // Nothing to accept before a proposal
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(Error::NoPendingAdmin))
+    );
+
+    client.propose_admin(&new_admin);
+    assert_eq!(client.pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.config().admin, Some(treasury.clone()));
+
+    // Only the proposed address may accept
+    assert_eq!(
+        client.try_accept_admin(&stranger),
+        Err(Ok(Error::UnauthorizedAccess))
+    );
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.config().admin, Some(new_admin.clone()));
+    assert_eq!(client.pending_admin(), None);
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(Error::NoPendingAdmin))
+    );
+}
+
+#[test]
+fn test_max_tvl_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+    client.set_max_tvl(&1000);
+    assert_eq!(client.config().max_tvl, 1000);
+
+    // Filling the cap exactly is accepted
+    let (depositor, recipient, token_address) = setup_funded_escrow(&env, &client, 86, &[600, 400]);
+    assert_eq!(client.total_value_locked(), 1000);
+
+    let milestones = |amount: i128| {
+        vec![
+            &env,
+            Milestone {
+                amount,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Task"),
+                depends_on: None,
+                released_at: None,
+                recipient: None,
+                deadline: None,
+                metadata_uri: None,
+            },
+        ]
+    };
+
+    client.create_escrow(
+        &87u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(600),
+        &1706400000u64,
+    );
+    let token_admin = token::StellarAssetClient::new(&env, &token_address);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &600);
+    token_client.approve(&depositor, &client.address, &600, &200);
+
+    // Creating an escrow commits nothing; funding it past the cap is refused
+    assert_eq!(client.total_value_locked(), 1000);
+    let result = client.try_deposit_funds(&87);
+    assert_eq!(result, Err(Ok(Error::TvlCapExceeded)));
+
+    // Released funds free up room under the cap
+    client.release_milestone(&86, &0, &token_address);
+    assert_eq!(client.total_value_locked(), 400);
+    client.deposit_funds(&87);
+    assert_eq!(client.total_value_locked(), 1000);
+
+    // Cancelling an escrow drops its deposit too
+    client.cancel_escrow(&87, &None);
+    assert_eq!(client.total_value_locked(), 400);
+}
+
+#[test]
+fn test_unfunded_escrows_do_not_consume_tvl_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+    client.set_max_tvl(&1000);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    // Unfunded escrows for the whole cap can be opened repeatedly
+    for escrow_id in 142u64..145 {
+        client.create_escrow(
+            &escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+    }
+    assert_eq!(client.total_value_locked(), 0);
+
+    // A real escrow can still be funded up to the cap
+    setup_funded_escrow(&env, &client, 145, &[1000]);
+    assert_eq!(client.total_value_locked(), 1000);
+}
+
+#[test]
+fn test_max_escrow_amount_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+    client.set_max_escrow_amount(&1000);
+    assert_eq!(client.config().max_escrow_amount, 1000);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = |amount: i128| {
+        vec![
+            &env,
+            Milestone {
+                amount,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Task"),
+                depends_on: None,
+                released_at: None,
+                recipient: None,
+                deadline: None,
+                metadata_uri: None,
+            },
+        ]
+    };
+
+    // Exactly at the limit is accepted
+    client.create_escrow(
+        &66u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(1000),
+        &1706400000u64,
+    );
+
+    let result = client.try_create_escrow(
+        &67u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(1001),
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::AmountExceedsLimit)));
+
+    // Zero lifts the cap
+    client.set_max_escrow_amount(&0);
+    client.create_escrow(
+        &67u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(1001),
+        &1706400000u64,
+    );
+}
+
+#[test]
+fn test_dispute_snapshot_preserves_pre_dispute_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 68u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[100, 200, 300, 400]);
+
+    let result = client.try_dispute_snapshot(&escrow_id, &1);
+    assert_eq!(result.err(), Some(Ok(Error::MilestoneNotDisputed)));
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    env.ledger().set_timestamp(5_000);
+    client.dispute_milestone(
+        &escrow_id,
+        &1,
+        &recipient,
+        &String::from_str(&env, "scope changed"),
+    );
+
+    // Later activity on the escrow does not touch the snapshot, nor does a
+    // dispute on another milestone
+    client.confirm_delivery(&escrow_id, &3, &depositor);
+    env.ledger().set_timestamp(6_000);
+    client.dispute_milestone(
+        &escrow_id,
+        &2,
+        &depositor,
+        &String::from_str(&env, "late delivery"),
+    );
+
+    let snapshot = client.dispute_snapshot(&escrow_id, &1);
+    assert_eq!(snapshot.milestone_index, 1);
+    assert_eq!(snapshot.taken_at, 5_000);
+    let statuses = [
+        (MilestoneStatus::Released, 100),
+        (MilestoneStatus::Pending, 200),
+        (MilestoneStatus::Pending, 300),
+        (MilestoneStatus::Pending, 400),
+    ];
+    assert_eq!(snapshot.milestones.len(), 4);
+    for (milestone, (status, amount)) in snapshot.milestones.iter().zip(statuses) {
+        assert_eq!(milestone.status, status);
+        assert_eq!(milestone.amount, amount);
+    }
+
+    // The second dispute has a snapshot of its own
+    let snapshot = client.dispute_snapshot(&escrow_id, &2);
+    assert_eq!(snapshot.milestone_index, 2);
+    assert_eq!(snapshot.taken_at, 6_000);
+    assert_eq!(
+        snapshot.milestones.get(1).unwrap().status,
+        MilestoneStatus::Disputed
+    );
+    assert_eq!(
+        snapshot.milestones.get(3).unwrap().status,
+        MilestoneStatus::Released
+    );
+}
+
+#[test]
+fn test_role_of() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    let escrow_id = 69u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_arbiter(&escrow_id, &arbiter, &100);
+
+    assert_eq!(
+        client.role_of(&escrow_id, &depositor),
+        EscrowRole::Depositor
+    );
+    assert_eq!(
+        client.role_of(&escrow_id, &recipient),
+        EscrowRole::Recipient
+    );
+    assert_eq!(client.role_of(&escrow_id, &arbiter), EscrowRole::Arbiter);
+    assert_eq!(
+        client.role_of(&escrow_id, &Address::generate(&env)),
+        EscrowRole::None
+    );
+}
+
+#[test]
+fn test_party_index_matches_party_addresses() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 101u64;
+    let (depositor, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[100]);
+    let outsider = Address::generate(&env);
+
+    assert_eq!(client.party_index(&escrow_id, &depositor), Some(0));
+    assert_eq!(client.party_index(&escrow_id, &recipient), Some(1));
+    assert_eq!(client.party_index(&escrow_id, &outsider), None);
+
+    let parties = client.party_addresses(&escrow_id);
+    assert_eq!(parties.get(1), Some(recipient.clone()));
+
+    env.as_contract(&contract_id, || {
+        let escrow = load_escrow(&env, escrow_id).unwrap();
+        assert_eq!(
+            find_party(&escrow, &recipient),
+            Some((1, EscrowRole::Recipient))
+        );
+        assert_eq!(find_party(&escrow, &outsider), None);
+    });
+}
+
+#[test]
+fn test_vesting_schedule_releases_linearly() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 70u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Grant"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    let invalid = VestingSchedule {
+        start: 1_000,
+        cliff: 2_000,
+        duration: 1_000,
+    };
+    let result = client.try_set_vesting(&escrow_id, &invalid);
+    assert_eq!(result, Err(Ok(Error::InvalidVestingSchedule)));
+
+    let schedule = VestingSchedule {
+        start: 1_000,
+        cliff: 100,
+        duration: 1_000,
+    };
+    client.set_vesting(&escrow_id, &schedule);
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Milestone releases are disabled on vesting escrows
+    let result = client.try_release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(result, Err(Ok(Error::VestingEscrow)));
+
+    // Before the cliff nothing has vested
+    env.ledger().set_timestamp(1_050);
+    assert_eq!(client.releasable_amount(&escrow_id), 0);
+    let result = client.try_claim_vested(&escrow_id);
+    assert_eq!(result, Err(Ok(Error::ZeroAmount)));
+
+    // Halfway through the schedule half has vested
+    env.ledger().set_timestamp(1_500);
+    assert_eq!(client.releasable_amount(&escrow_id), 500);
+    assert_eq!(client.claim_vested(&escrow_id), 500);
+    assert_eq!(token_client.balance(&recipient), 500);
+    assert_eq!(client.releasable_amount(&escrow_id), 0);
+
+    // After the duration the rest is claimable and the escrow can complete
+    env.ledger().set_timestamp(3_000);
+    assert_eq!(client.releasable_amount(&escrow_id), 500);
+    client.claim_vested(&escrow_id);
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(
+        escrow.milestones.get(0).unwrap().status,
+        MilestoneStatus::Released
+    );
+    client.complete_escrow(&escrow_id);
+    assert_eq!(client.get_state(&escrow_id), EscrowStatus::Completed);
+}
+
+#[contract]
+struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn set_target(env: Env, escrow: Address, escrow_id: u64, buyer: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("target"), &(escrow, escrow_id, buyer));
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        env.storage().persistent().set(&to, &amount);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, amount: i128) {
+        Self::move_funds(&env, from, to, amount);
+    }
+
+    // Tries to release a second milestone while the first one is being paid
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        Self::move_funds(&env, from, to, amount);
+        let (escrow, escrow_id, buyer): (Address, u64, Address) = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("target"))
+            .unwrap();
+        let reentered = VaultixEscrowClient::new(&env, &escrow)
+            .try_confirm_delivery(&escrow_id, &1, &buyer)
+            .is_ok();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("reenter"), &reentered);
+    }
+
+    pub fn reentered(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("reenter"))
+            .unwrap_or(false)
+    }
+
+    fn move_funds(env: &Env, from: Address, to: Address, amount: i128) {
+        let from_balance = Self::balance(env.clone(), from.clone());
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&from, &(from_balance - amount));
+        env.storage().persistent().set(&to, &(to_balance + amount));
+    }
+}
+
+#[test]
+fn test_reentrant_token_cannot_release_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let token_address = env.register(ReentrantToken, ());
+    let token = ReentrantTokenClient::new(&env, &token_address);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    let escrow_id = 71u64;
+    token.mint(&depositor, &200);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.deposit_funds(&escrow_id);
+    token.set_target(&contract_id, &escrow_id, &depositor);
+
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+
+    // The nested release was rejected while the outer one completed
+    assert!(!token.reentered());
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.total_released, 100);
+    assert_eq!(
+        escrow.milestones.get(1).unwrap().status,
+        MilestoneStatus::Pending
+    );
+}
+
+#[test]
+fn test_lock_blocks_state_changes_without_fund_movement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 207u64;
+    let (depositor, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[100, 200]);
+
+    // Hold the lock as a fund-moving call would while it pays out
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("locked"), &true);
+    });
+
+    let locked = Some(Ok(Error::ReentrancyDetected));
+    let note = String::from_str(&env, "mid-release");
+    let proof = BytesN::from_array(&env, &[3; 32]);
+    assert_eq!(
+        client.try_add_note(&escrow_id, &depositor, &note).err(),
+        locked
+    );
+    assert_eq!(
+        client.try_submit_delivery(&escrow_id, &0, &proof).err(),
+        locked
+    );
+    assert_eq!(
+        client.try_start_milestone(&escrow_id, &0, &recipient).err(),
+        locked
+    );
+    assert_eq!(
+        client
+            .try_pause_escrow(&escrow_id, &depositor, &symbol_short!("audit"))
+            .err(),
+        locked
+    );
+    assert_eq!(client.try_update_fee(&100).err(), locked);
+    assert_eq!(
+        client
+            .try_dispute_milestone(&escrow_id, &1, &recipient, &note)
+            .err(),
+        locked
+    );
+
+    // Once the lock is released the same calls go through
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&symbol_short!("locked"));
+    });
+    client.add_note(&escrow_id, &depositor, &note);
+    assert_eq!(client.get_notes(&escrow_id).len(), 1);
+}
+
+#[test]
+fn test_completion_percentage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 72u64;
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[500, 500]);
+
+    assert_eq!(client.completion_percentage(&escrow_id), 0);
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(client.completion_percentage(&escrow_id), 50);
+    client.release_milestone(&escrow_id, &1, &token_address);
+    assert_eq!(client.completion_percentage(&escrow_id), 100);
+}
+
+#[test]
+fn test_cancel_rejects_closed_escrows() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    // Cancelling twice is rejected
+    setup_funded_escrow(&env, &client, 73u64, &[500]);
+    client.cancel_escrow(&73u64, &None);
+    let result = client.try_cancel_escrow(&73u64, &None);
+    assert_eq!(result, Err(Ok(Error::EscrowNotActive)));
+
+    // So is cancelling a completed escrow
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, 74u64, &[500]);
+    client.release_milestone(&74u64, &0, &token_address);
+    client.complete_escrow(&74u64);
+    let result = client.try_cancel_escrow(&74u64, &None);
+    assert_eq!(result, Err(Ok(Error::EscrowNotActive)));
+}
+
+#[test]
+fn test_recipient_whitelist_restricts_payouts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let subcontractor = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 100u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestone = |amount: i128, payee: Option<Address>| Milestone {
+        amount,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Task"),
+        depends_on: None,
+        released_at: None,
+        recipient: payee,
+        deadline: None,
+        metadata_uri: None,
+    };
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &vec![
+            &env,
+            milestone(400, None),
+            milestone(600, Some(subcontractor.clone())),
+        ],
+        &1706400000u64,
+    );
+    client.set_recipient_whitelist(&escrow_id, &vec![&env, recipient.clone()]);
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // The subcontractor was never whitelisted
+    assert!(!client.is_releasable(&escrow_id, &1));
+    assert!(client.is_releasable(&escrow_id, &0));
+    assert_eq!(
+        client.try_release_milestone(&escrow_id, &1, &token_address),
+        Err(Ok(Error::RecipientNotWhitelisted))
+    );
+    assert_eq!(token_client.balance(&subcontractor), 0);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(token_client.balance(&recipient), 400);
+}
+
+#[test]
+fn test_milestone_recipients() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let subcontractor = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 75u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestone = |amount: i128, payee: Option<Address>| Milestone {
+        amount,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Task"),
+        depends_on: None,
+        released_at: None,
+        recipient: payee,
+        deadline: None,
+        metadata_uri: None,
+    };
+
+    // The depositor cannot be paid through a milestone either
+    let result = client.try_create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &vec![&env, milestone(400, Some(depositor.clone()))],
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::SelfDealing)));
+
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &vec![
+            &env,
+            milestone(400, None),
+            milestone(600, Some(subcontractor.clone())),
+        ],
+        &1706400000u64,
+    );
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    client.confirm_delivery(&escrow_id, &1, &depositor);
+
+    assert_eq!(token_client.balance(&recipient), 400);
+    assert_eq!(token_client.balance(&subcontractor), 600);
+}
+
+#[test]
+fn test_over_release_guard() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 76u64;
+    setup_funded_escrow(&env, &client, escrow_id, &[300, 700]);
+
+    env.as_contract(&contract_id, || {
+        let mut escrow = load_escrow(&env, escrow_id).unwrap();
+
+        // Simulate bookkeeping drift where a milestone was counted twice
+        add_released(&mut escrow, 700).unwrap();
+        add_released(&mut escrow, 300).unwrap();
+        assert_eq!(add_released(&mut escrow, 1), Err(Error::OverReleased));
+        assert_eq!(escrow.total_released, 1000);
+    });
+}
+
+#[test]
+fn test_validate_index_boundaries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 93u64;
+    setup_funded_escrow(&env, &client, escrow_id, &[100, 200, 300]);
+
+    env.as_contract(&contract_id, || {
+        let escrow = load_escrow(&env, escrow_id).unwrap();
+
+        assert_eq!(validate_index(&escrow, 0), Ok(()));
+        assert_eq!(validate_index(&escrow, 2), Ok(()));
+        assert_eq!(validate_index(&escrow, 3), Err(Error::MilestoneNotFound));
+        assert_eq!(
+            validate_index(&escrow, u32::MAX),
+            Err(Error::MilestoneNotFound)
+        );
+        assert_eq!(milestone_at(&escrow, 2).unwrap().amount, 300);
+        assert_eq!(
+            milestone_at(&escrow, 3).err(),
+            Some(Error::MilestoneNotFound)
+        );
+    });
+
+    // Every index-taking entry point reports the same error past the end
+    assert_eq!(
+        client.try_get_milestone(&escrow_id, &3).err(),
+        Some(Ok(Error::MilestoneNotFound))
+    );
+    assert_eq!(
+        client.try_milestone_context(&escrow_id, &3).err(),
+        Some(Ok(Error::MilestoneNotFound))
+    );
+    assert_eq!(
+        client.try_time_until_releasable(&escrow_id, &3),
+        Err(Ok(Error::MilestoneNotFound))
+    );
+    assert_eq!(
+        client.try_submit_delivery(&escrow_id, &3, &BytesN::from_array(&env, &[1; 32])),
+        Err(Ok(Error::MilestoneNotFound))
+    );
+}
+
+#[test]
+fn test_verify_invariants() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+
+    // Healthy escrows pass at every stage
+    let escrow_id = 105u64;
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+    assert!(client.verify_invariants(&escrow_id));
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert!(client.verify_invariants(&escrow_id));
+    client.release_all(&escrow_id);
+    assert!(client.verify_invariants(&escrow_id));
+
+    // Bookkeeping drift is detected
+    setup_funded_escrow(&env, &client, 106, &[1000, 2000]);
+    env.as_contract(&contract_id, || {
+        let mut escrow = load_escrow(&env, 106).unwrap();
+        escrow.total_released = 500;
+        env.storage()
+            .persistent()
+            .set(&get_storage_key(106), &escrow);
+    });
+    assert!(!client.verify_invariants(&106));
+}
+
+#[test]
+fn test_interactions_extend_escrow_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 77u64;
+    let (depositor, _, _) = setup_funded_escrow(&env, &client, escrow_id, &[300, 700]);
+    let escrow_ttl = || {
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&get_storage_key(escrow_id))
+        })
+    };
+
+    // Move to within 50 ledgers of the escrow's expiry
+    let sequence = env.ledger().sequence();
+    env.ledger()
+        .set_sequence_number(sequence + escrow_ttl() - 50);
+    assert_eq!(escrow_ttl(), 50);
+
+    // Confirming a delivery refreshes the escrow's TTL
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(escrow_ttl(), 2_000_000);
+
+    // So does an explicit bump
+    let sequence = env.ledger().sequence();
+    env.ledger().set_sequence_number(sequence + 1_999_950);
+    client.bump_escrow(&escrow_id);
+    assert_eq!(escrow_ttl(), 2_000_000);
+
+    // Records stored alongside the escrow are kept alive with it
+    client.submit_delivery(&escrow_id, &1, &BytesN::from_array(&env, &[7; 32]));
+    client.add_note(&escrow_id, &depositor, &String::from_str(&env, "kickoff"));
+    let record_ttls = || {
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            (
+                storage.get_ttl(&get_proof_key(escrow_id, 1)),
+                storage.get_ttl(&get_notes_key(escrow_id)),
+            )
+        })
+    };
+    let sequence = env.ledger().sequence();
+    env.ledger()
+        .set_sequence_number(sequence + record_ttls().0 - 50);
+    client.bump_escrow(&escrow_id);
+    assert_eq!(record_ttls(), (2_000_000, 2_000_000));
+}
+
+#[test]
+fn test_release_and_dispute_keep_side_records_alive() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 205u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[100, 200, 300]);
+    client.dispute_milestone(
+        &escrow_id,
+        &0,
+        &recipient,
+        &String::from_str(&env, "scope changed"),
+    );
+    let dispute_ttls = || {
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            (
+                storage.get_ttl(&get_dispute_key(escrow_id, 0)),
+                storage.get_ttl(&get_snapshot_key(escrow_id, 0)),
+            )
+        })
+    };
+
+    // A release on another milestone refreshes the open dispute's records
+    let sequence = env.ledger().sequence();
+    env.ledger()
+        .set_sequence_number(sequence + dispute_ttls().0 - 50);
+    client.release_milestone(&escrow_id, &1, &token_address);
+    assert_eq!(dispute_ttls(), (2_000_000, 2_000_000));
+
+    // So does a dispute on another milestone
+    let sequence = env.ledger().sequence();
+    env.ledger().set_sequence_number(sequence + 1_999_950);
+    client.dispute_milestone(
+        &escrow_id,
+        &2,
+        &depositor,
+        &String::from_str(&env, "late delivery"),
+    );
+    assert_eq!(dispute_ttls(), (2_000_000, 2_000_000));
+    assert_eq!(client.dispute_snapshot(&escrow_id, &0).milestone_index, 0);
+}
+
+#[test]
+fn test_estimated_completion() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 78u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &600);
+
+    let milestone = |deadline: Option<u64>| Milestone {
+        amount: 200,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Task"),
+        depends_on: None,
+        released_at: None,
+        recipient: None,
+        deadline,
+        metadata_uri: None,
+    };
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &vec![
+            &env,
+            milestone(Some(3_000)),
+            milestone(None),
+            milestone(Some(2_000)),
+        ],
+        &1706400000u64,
+    );
+    token_client.approve(&depositor, &contract_id, &600, &200);
+    client.deposit_funds(&escrow_id);
+
+    assert_eq!(client.estimated_completion(&escrow_id), Some(3_000));
+
+    // Released milestones drop out of the estimate
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(client.estimated_completion(&escrow_id), Some(2_000));
+
+    client.release_milestone(&escrow_id, &2, &token_address);
+    assert_eq!(client.estimated_completion(&escrow_id), None);
+}
+
+#[test]
+fn test_creator_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &None);
+
+    let vetted = Address::generate(&env);
+    let unlisted = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    client.add_allowed_creator(&vetted);
+    assert_eq!(client.allowed_creators(), vec![&env, vetted.clone()]);
+
+    let result = client.try_create_escrow(
+        &79u64,
+        &unlisted,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::CreatorNotAllowed)));
+
+    client.create_escrow(
+        &79u64,
+        &vetted,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    // Requests are checked against the recipient creating them, not the
+    // depositor they name
+    let result = client.try_request_escrow(
+        &80u64,
+        &unlisted,
+        &vetted,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::CreatorNotAllowed)));
+
+    // Emptying the list reopens creation
+    client.remove_allowed_creator(&vetted);
+    client.create_escrow(
+        &80u64,
+        &unlisted,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+}
+
+#[test]
+fn test_start_milestone_then_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 81u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[400, 600]);
+
+    let result = client.try_start_milestone(&escrow_id, &0, &depositor);
+    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+
+    client.start_milestone(&escrow_id, &0, &recipient);
+    assert_eq!(
+        client
+            .get_escrow(&escrow_id)
+            .milestones
+            .get(0)
+            .unwrap()
+            .status,
+        MilestoneStatus::InProgress
+    );
+
+    let result = client.try_start_milestone(&escrow_id, &0, &recipient);
+    assert_eq!(result, Err(Ok(Error::MilestoneAlreadyStarted)));
+
+    // In-progress and pending milestones both release normally
+    client.release_milestone(&escrow_id, &0, &token_address);
+    client.release_milestone(&escrow_id, &1, &token_address);
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(
+        escrow.milestones.get(0).unwrap().status,
+        MilestoneStatus::Released
+    );
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&recipient),
+        1000
+    );
+}
+
+#[test]
+fn test_confirm_delivery_requires_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 82u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_require_proof(&escrow_id, &true);
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Without a proof the buyer cannot confirm
+    let result = client.try_confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(result, Err(Ok(Error::ConditionsNotMet)));
+
+    let proof = BytesN::from_array(&env, &[7u8; 32]);
+    client.submit_delivery(&escrow_id, &0, &proof);
+    assert_eq!(client.delivery_proof(&escrow_id, &0), Some(proof));
+
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_escrows_by_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let xlm = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    for (escrow_id, token) in [(83u64, &usdc), (84u64, &xlm), (85u64, &usdc)] {
+        client.create_escrow(
+            &escrow_id,
+            &depositor,
+            &recipient,
+            token,
+            &milestones,
+            &1706400000u64,
+        );
+    }
+
+    assert_eq!(
+        client.escrows_by_token(&usdc, &0, &10),
+        vec![&env, 83u64, 85u64]
+    );
+    assert_eq!(client.escrows_by_token(&xlm, &0, &10), vec![&env, 84u64]);
+    assert_eq!(client.escrows_by_token(&usdc, &0, &1), vec![&env, 83u64]);
+}
+
+#[test]
+fn test_escrows_of_depositor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    for (escrow_id, depositor) in [(102u64, &alice), (103u64, &bob), (104u64, &alice)] {
+        client.create_escrow(
+            &escrow_id,
+            depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+    }
+
+    assert_eq!(
+        client.escrows_of_depositor(&alice, &0, &10),
+        vec![&env, 102u64, 104u64]
+    );
+    assert_eq!(
+        client.escrows_of_depositor(&bob, &0, &10),
+        vec![&env, 103u64]
+    );
+    assert_eq!(
+        client.escrows_of_depositor(&alice, &1, &10),
+        vec![&env, 104u64]
+    );
+    assert!(client.escrows_of_depositor(&recipient, &0, &10).is_empty());
+}
+
+// Creates a funded two-milestone escrow whose first milestone is disputed
+// by the recipient at t = 1_700_000_000, with a 1000 second dispute window.
+fn setup_stale_dispute(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    escrow_id: u64,
+    default: DisputeDefault,
+) -> (Address, Address, token::Client<'static>) {
+    let depositor = Address::generate(env);
+    let recipient = Address::generate(env);
+    let admin = Address::generate(env);
+
+    let (token_admin, token_address) = create_test_token(env, &admin);
+    let token_client = token::Client::new(env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let mut milestones = Vec::new(env);
+    for amount in [1000i128, 2000] {
+        milestones.push_back(Milestone {
+            amount,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        });
+    }
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_dispute_default(&escrow_id, &1000, &default);
+    token_client.approve(&depositor, &client.address, &3000, &200);
+    client.deposit_funds(&escrow_id);
+
+    env.ledger().set_timestamp(1_700_000_000);
+    let reason = String::from_str(env, "no delivery");
+    client.dispute_milestone(&escrow_id, &0, &recipient, &reason);
+
+    (depositor, recipient, token_client)
+}
+
+#[test]
+fn test_stale_dispute_defaults_to_depositor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 109u64;
+    let (depositor, recipient, token_client) =
+        setup_stale_dispute(&env, &client, escrow_id, DisputeDefault::Depositor);
+
+    // The arbiter keeps the dispute until the window has elapsed
+    env.ledger().set_timestamp(1_700_001_000);
+    assert_eq!(
+        client.try_resolve_stale_dispute(&escrow_id, &0),
+        Err(Ok(Error::ActionTooSoon))
+    );
+    assert_eq!(
+        client.try_resolve_stale_dispute(&escrow_id, &1),
+        Err(Ok(Error::MilestoneNotDisputed))
+    );
+
+    env.ledger().set_timestamp(1_700_001_001);
+    client.resolve_stale_dispute(&escrow_id, &0);
+
+    assert_eq!(token_client.balance(&depositor), 1000);
+    assert_eq!(token_client.balance(&recipient), 0);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(
+        escrow.milestones.get(0).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+    assert_eq!(escrow.total_refunded, 1000);
+    assert!(client.dispute_details(&escrow_id, &0).unwrap().resolved);
+}
+
+#[test]
+fn test_stale_dispute_defaults_to_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 110u64;
+    let (depositor, recipient, token_client) =
+        setup_stale_dispute(&env, &client, escrow_id, DisputeDefault::Recipient);
+
+    env.ledger().set_timestamp(1_700_001_001);
+    client.resolve_stale_dispute(&escrow_id, &0);
+
+    assert_eq!(token_client.balance(&depositor), 0);
+    assert_eq!(token_client.balance(&recipient), 1000);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(
+        escrow.milestones.get(0).unwrap().status,
+        MilestoneStatus::Released
+    );
+    assert_eq!(escrow.total_released, 1000);
+
+    // A settled dispute cannot be applied twice
+    assert_eq!(
+        client.try_resolve_stale_dispute(&escrow_id, &0),
+        Err(Ok(Error::MilestoneNotDisputed))
+    );
+}
+
+#[test]
+fn test_stale_dispute_requires_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 111u64;
+    let (_, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    let reason = String::from_str(&env, "no delivery");
+    client.dispute_milestone(&escrow_id, &0, &recipient, &reason);
+
+    env.ledger().set_timestamp(u64::MAX);
+    assert_eq!(
+        client.try_resolve_stale_dispute(&escrow_id, &0),
+        Err(Ok(Error::ActionTooSoon))
+    );
+}
+
+#[test]
+fn test_min_milestone_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+    client.set_min_milestone_amount(&100);
+    assert_eq!(client.config().min_milestone_amount, 100);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (_, token_address) = create_test_token(&env, &admin);
+
+    let milestones = |amount: i128| {
+        vec![
+            &env,
+            Milestone {
+                amount: 500,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Main"),
+                depends_on: None,
+                released_at: None,
+                recipient: None,
+                deadline: None,
+                metadata_uri: None,
+            },
+            Milestone {
+                amount,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Dust"),
+                depends_on: None,
+                released_at: None,
+                recipient: None,
+                deadline: None,
+                metadata_uri: None,
+            },
+        ]
+    };
+
+    let result = client.try_create_escrow(
+        &112u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(99),
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::MilestoneTooSmall)));
+
+    // A milestone exactly at the minimum is accepted
+    client.create_escrow(
+        &112u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(100),
+        &1706400000u64,
+    );
+    assert_eq!(client.get_escrow(&112).total_amount, 600);
+
+    // Zero disables the check again
+    client.set_min_milestone_amount(&0);
+    client.create_escrow(
+        &113u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(1),
+        &1706400000u64,
+    );
+}
+
+// Token that burns a configurable cut of every transfer
+#[contract]
+struct FeeOnTransferToken;
+
+#[contractimpl]
+impl FeeOnTransferToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().persistent().set(&to, &(balance + amount));
+    }
+
+    pub fn set_fee(env: Env, fee_bps: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("fee"), &fee_bps);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        Self::move_funds(&env, &from, &to, amount);
+    }
+
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        Self::move_funds(&env, &from, &to, amount);
+    }
+
+    fn move_funds(env: &Env, from: &Address, to: &Address, amount: i128) {
+        let fee_bps: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("fee"))
+            .unwrap_or(0);
+        let from_balance = Self::balance(env.clone(), from.clone());
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(from, &(from_balance - amount));
+        env.storage()
+            .persistent()
+            .set(to, &(to_balance + amount - amount * fee_bps / 10_000));
+    }
+}
+
+#[test]
+fn test_fee_on_transfer_token_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let token_address = env.register(FeeOnTransferToken, ());
+    let token = FeeOnTransferTokenClient::new(&env, &token_address);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token.mint(&depositor, &2000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    for escrow_id in [114u64, 115] {
+        client.create_escrow(
+            &escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+    }
+
+    // A deposit that arrives short is rejected outright
+    token.set_fee(&100);
+    assert_eq!(
+        client.try_deposit_funds(&114),
+        Err(Ok(Error::TokenTransferFailed))
+    );
+    assert_eq!(token.balance(&depositor), 2000);
+
+    // So is a payout that reaches the recipient short
+    token.set_fee(&0);
+    client.deposit_funds(&115);
+    token.set_fee(&100);
+    assert_eq!(
+        client.try_release_milestone(&115, &0, &token_address),
+        Err(Ok(Error::TokenTransferFailed))
+    );
+    assert_eq!(token.balance(&recipient), 0);
+    assert_eq!(token.balance(&contract_id), 1000);
+}
+
+#[test]
+fn test_milestone_context_for_disputed_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 116u64;
+    let (_, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+    let reason = String::from_str(&env, "incomplete work");
+    client.dispute_milestone(&escrow_id, &1, &recipient, &reason);
+
+    let context = client.milestone_context(&escrow_id, &1);
+    assert_eq!(context.index, 1);
+    assert_eq!(context.milestone.amount, 2000);
+    assert_eq!(context.milestone.status, MilestoneStatus::Disputed);
+    assert_eq!(context.escrow_status, EscrowStatus::Active);
+    assert!(context.is_disputed);
+
+    assert!(!client.milestone_context(&escrow_id, &0).is_disputed);
+    assert_eq!(
+        client.try_milestone_context(&escrow_id, &2).err(),
+        Some(Ok(Error::MilestoneNotFound))
+    );
+}
+
+#[test]
+fn test_currency_metadata_round_trips() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let escrow_id = 117u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.token_symbol, None);
+    assert_eq!(escrow.token_decimals, None);
+
+    client.set_currency_metadata(&escrow_id, &symbol_short!("USDC"), &7);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.token_symbol, Some(symbol_short!("USDC")));
+    assert_eq!(escrow.token_decimals, Some(7));
+
+    // Display metadata is fixed once the escrow is funded
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+    assert_eq!(
+        client.try_set_currency_metadata(&escrow_id, &symbol_short!("XLM"), &7),
+        Err(Ok(Error::EscrowAlreadyFunded))
+    );
+}
+
+#[test]
+fn test_recipient_declines_unfunded_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&recipient, &200);
+
+    let escrow_id = 118u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_recipient_bond(&escrow_id, &200);
+    client.post_bond(&escrow_id);
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    // Only the recipient may decline
+    assert_eq!(
+        client.try_decline_escrow(&escrow_id, &depositor),
+        Err(Ok(Error::UnauthorizedAccess))
+    );
+
+    client.decline_escrow(&escrow_id, &recipient);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Cancelled);
+    assert_eq!(escrow.bond_status, BondStatus::Returned);
+    assert_eq!(token_client.balance(&recipient), 200);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_decline_rejected_after_funding() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 119u64;
+    let (_, recipient, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    assert_eq!(
+        client.try_decline_escrow(&escrow_id, &recipient),
+        Err(Ok(Error::EscrowAlreadyFunded))
+    );
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    client.complete_escrow(&escrow_id);
+    assert_eq!(
+        client.try_decline_escrow(&escrow_id, &recipient),
+        Err(Ok(Error::EscrowAlreadyFunded))
+    );
+}
+
+#[test]
+fn test_statuses_of_many_escrows() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    setup_funded_escrow(&env, &client, 120, &[100, 200]);
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, 121, &[100, 200, 300]);
+    setup_funded_escrow(&env, &client, 122, &[100]);
+    client.release_milestone(&121, &0, &token_address);
+    client.release_milestone(&121, &2, &token_address);
+    client.cancel_escrow(&122, &None);
+
+    let statuses = client.statuses_of(&vec![&env, 120u64, 121, 999, 122]);
+    assert_eq!(
+        statuses,
+        vec![
+            &env,
+            (120u64, EscrowStatus::Active, 0u32),
+            (121, EscrowStatus::Active, 2),
+            (122, EscrowStatus::Cancelled, 0),
+        ]
+    );
+
+    let mut too_many = Vec::new(&env);
+    for id in 0..51u64 {
+        too_many.push_back(id);
+    }
+    assert_eq!(
+        client.try_statuses_of(&too_many),
+        Err(Ok(Error::VectorTooLarge))
+    );
+}
+
+// Creates a funded single-milestone escrow with auto-release enabled and a
+// delivery submitted at t = 1_700_000_000
+fn setup_auto_release(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    escrow_id: u64,
+) -> (Address, Address, token::Client<'static>) {
+    let depositor = Address::generate(env);
+    let recipient = Address::generate(env);
+    let admin = Address::generate(env);
+    let (token_admin, token_address) = create_test_token(env, &admin);
+    let token_client = token::Client::new(env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestones = vec![
+        env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Sale"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_auto_release(&escrow_id, &true);
+    token_client.approve(&depositor, &client.address, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    env.ledger().set_timestamp(1_700_000_000);
+    client.submit_delivery(&escrow_id, &0, &BytesN::from_array(env, &[7; 32]));
+
+    (depositor, recipient, token_client)
+}
+
+#[test]
+fn test_auto_release_after_challenge_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 123u64;
+    let (_, recipient, token_client) = setup_auto_release(&env, &client, escrow_id);
+
+    env.ledger()
+        .set_timestamp(1_700_000_000 + AUTO_RELEASE_WINDOW);
+    assert_eq!(
+        client.try_claim_auto_release(&escrow_id),
+        Err(Ok(Error::ActionTooSoon))
+    );
+
+    env.ledger()
+        .set_timestamp(1_700_000_001 + AUTO_RELEASE_WINDOW);
+    client.claim_auto_release(&escrow_id);
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert_eq!(
+        client
+            .get_escrow(&escrow_id)
+            .milestones
+            .get(0)
+            .unwrap()
+            .status,
+        MilestoneStatus::Released
+    );
+    assert_eq!(
+        client.try_claim_auto_release(&escrow_id),
+        Err(Ok(Error::MilestoneAlreadyReleased))
+    );
+}
+
+#[test]
+fn test_dispute_blocks_auto_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 124u64;
+    let (depositor, recipient, token_client) = setup_auto_release(&env, &client, escrow_id);
+
+    env.ledger()
+        .set_timestamp(1_700_000_000 + AUTO_RELEASE_WINDOW / 2);
+    let reason = String::from_str(&env, "item not received");
+    client.dispute_milestone(&escrow_id, &0, &depositor, &reason);
+
+    env.ledger()
+        .set_timestamp(1_700_000_001 + AUTO_RELEASE_WINDOW);
+    assert_eq!(
+        client.try_claim_auto_release(&escrow_id),
+        Err(Ok(Error::MilestoneDisputed))
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+}
+
+#[test]
+fn test_auto_release_requires_single_milestone() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 125u64;
+    let (_, recipient, _) = setup_funded_escrow(&env, &client, escrow_id, &[500]);
+    client.submit_delivery(&escrow_id, &0, &BytesN::from_array(&env, &[7; 32]));
+    assert_eq!(
+        client.try_claim_auto_release(&escrow_id),
+        Err(Ok(Error::AutoReleaseUnsupported))
+    );
+
+    let depositor = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (_, token_address) = create_test_token(&env, &admin);
+    let mut milestones = Vec::new(&env);
+    for _ in 0..2 {
+        milestones.push_back(Milestone {
+            amount: 500,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        });
+    }
+    client.create_escrow(
+        &126u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(
+        client.try_set_auto_release(&126, &true),
+        Err(Ok(Error::AutoReleaseUnsupported))
+    );
+}
+
+#[test]
+fn test_reconcile_restores_total_released() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 127u64;
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+    client.release_milestone(&escrow_id, &1, &token_address);
+
+    env.as_contract(&contract_id, || {
+        let mut escrow = load_escrow(&env, escrow_id).unwrap();
+        escrow.total_released = 500;
+        env.storage()
+            .persistent()
+            .set(&get_storage_key(escrow_id), &escrow);
+    });
+    assert!(!client.verify_invariants(&escrow_id));
+
+    client.reconcile(&escrow_id);
+    assert_eq!(env.auths()[0].0, treasury);
+
+    // The event records the drifted and the recomputed totals
+    let events = env.events().all().filter_by_contract(&contract_id);
+    let ContractEventBody::V0(body) = &events.events().last().unwrap().body;
+    let expected: Val = (500i128, 2000i128).into_val(&env);
+    assert_eq!(body.data, ScVal::try_from_val(&env, &expected).unwrap());
+
+    assert_eq!(client.get_escrow(&escrow_id).total_released, 2000);
+    assert!(client.verify_invariants(&escrow_id));
+}
+
+#[test]
+fn test_time_until_releasable_counts_down() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let mut milestones = Vec::new(&env);
+    for _ in 0..3 {
+        milestones.push_back(Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        });
+    }
+    let escrow_id = 128u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_release_cooldown(&escrow_id, &10);
+
+    // Unfunded escrows cannot be released by waiting
+    assert_eq!(client.time_until_releasable(&escrow_id, &0), None);
+
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+    assert_eq!(client.time_until_releasable(&escrow_id, &0), Some(0));
+
+    env.ledger().set_sequence_number(100);
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(client.time_until_releasable(&escrow_id, &0), None);
+
+    // The cooldown counts down ledger by ledger
+    env.ledger().set_sequence_number(104);
+    assert_eq!(client.time_until_releasable(&escrow_id, &1), Some(30));
+    assert!(!client.is_releasable(&escrow_id, &1));
+
+    env.ledger().set_sequence_number(110);
+    assert_eq!(client.time_until_releasable(&escrow_id, &1), Some(0));
+    assert!(client.is_releasable(&escrow_id, &1));
+
+    // A disputed milestone never unlocks by itself
+    let reason = String::from_str(&env, "late");
+    client.dispute_milestone(&escrow_id, &2, &recipient, &reason);
+    assert_eq!(client.time_until_releasable(&escrow_id, &2), None);
+    assert_eq!(
+        client.try_time_until_releasable(&escrow_id, &3),
+        Err(Ok(Error::MilestoneNotFound))
+    );
+}
+
+#[test]
+fn test_time_until_releasable_waits_for_vesting_cliff() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Grant"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    let escrow_id = 129u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_vesting(
+        &escrow_id,
+        &VestingSchedule {
+            start: 1_000,
+            cliff: 100,
+            duration: 1_000,
+        },
+    );
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    env.ledger().set_timestamp(1_040);
+    assert_eq!(client.time_until_releasable(&escrow_id, &0), Some(60));
+
+    env.ledger().set_timestamp(1_100);
+    assert_eq!(client.time_until_releasable(&escrow_id, &0), Some(0));
+    assert!(client.releasable_amount(&escrow_id) > 0);
+}
+
+#[test]
+fn test_milestone_metadata_uris_round_trip() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (_, token_address) = create_test_token(&env, &admin);
+
+    let milestone = |uri: Option<String>| Milestone {
+        amount: 500,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Task"),
+        depends_on: None,
+        released_at: None,
+        recipient: None,
+        deadline: None,
+        metadata_uri: uri,
+    };
+    let spec = String::from_str(&env, "ipfs://bafy-spec");
+    let design = String::from_str(&env, "https://example.com/design.pdf");
+    let milestones = vec![
+        &env,
+        milestone(Some(spec.clone())),
+        milestone(None),
+        milestone(Some(design.clone())),
+    ];
+    client.create_escrow(
+        &130u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    assert_eq!(client.get_milestone(&130, &0).metadata_uri, Some(spec));
+    assert_eq!(client.get_milestone(&130, &1).metadata_uri, None);
+    assert_eq!(client.get_milestone(&130, &2).metadata_uri, Some(design));
+    assert_eq!(
+        client.try_get_milestone(&130, &3).err(),
+        Some(Ok(Error::MilestoneNotFound))
+    );
+
+    // Empty and oversized URIs are rejected
+    for uri in [
+        String::from_str(&env, ""),
+        String::from_str(&env, &"a".repeat(257)),
+    ] {
+        let result = client.try_create_escrow(
+            &131u64,
+            &depositor,
+            &recipient,
+            &token_address,
+            &vec![&env, milestone(Some(uri))],
+            &1706400000u64,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidMetadataUri)));
+    }
+}
+
+#[test]
+fn test_features_reflect_configuration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    // Before initialize there is no fee collector to charge fees to
+    let features = client.features();
+    assert!(!features.fees);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+    assert_eq!(
+        client.features(),
+        FeatureFlags {
+            fees: true,
+            dispute_fees: false,
+            creator_allowlist: false,
+            token_allowlist: false,
+            amount_cap: false,
+            tvl_cap: false,
+            min_milestone: false,
+        }
+    );
+
+    client.set_dispute_fee(&10);
+    client.add_allowed_token(&Address::generate(&env));
+    client.add_allowed_creator(&Address::generate(&env));
+    client.set_max_escrow_amount(&1_000_000);
+    client.set_max_tvl(&10_000_000);
+    client.set_min_milestone_amount(&100);
+    assert_eq!(
+        client.features(),
+        FeatureFlags {
+            fees: true,
+            dispute_fees: true,
+            creator_allowlist: true,
+            token_allowlist: true,
+            amount_cap: true,
+            tvl_cap: true,
+            min_milestone: true,
+        }
+    );
+}
+
+#[test]
+fn test_mutual_cancel_milestone_refunds_depositor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 132u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+    let token_client = token::Client::new(&env, &token_address);
+
+    client.mutual_cancel_milestone(&escrow_id, &1);
+    let auths = env.auths();
+    assert!(auths.iter().any(|(address, _)| *address == depositor));
+    assert!(auths.iter().any(|(address, _)| *address == recipient));
+
+    assert_eq!(token_client.balance(&depositor), 2000);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(
+        escrow.milestones.get(1).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+    assert_eq!(escrow.milestones.get(1).unwrap().amount, 0);
+    assert_eq!(escrow.total_amount, 1000);
+    assert_eq!(escrow.total_refunded, 0);
+    assert_eq!(client.total_value_locked(), 1000);
+    assert!(client.verify_invariants(&escrow_id));
+    assert_eq!(
+        client.try_mutual_cancel_milestone(&escrow_id, &1),
+        Err(Ok(Error::MilestoneAlreadyReleased))
+    );
+
+    // The remaining milestone still releases and completes the escrow
+    client.release_milestone(&escrow_id, &0, &token_address);
+    client.complete_escrow(&escrow_id);
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_mutual_cancel_milestone_needs_both_parties() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 133u64;
+    let (depositor, _, _) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+
+    // Only the depositor signs
+    env.mock_auths(&[MockAuth {
+        address: &depositor,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "mutual_cancel_milestone",
+            args: (escrow_id, 1u32).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_mutual_cancel_milestone(&escrow_id, &1).is_err());
+
+    env.mock_all_auths();
+    assert_eq!(
+        client
+            .get_escrow(&escrow_id)
+            .milestones
+            .get(1)
+            .unwrap()
+            .status,
+        MilestoneStatus::Pending
+    );
+}
+
+#[test]
+fn test_release_with_ref_is_idempotent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 134u64;
+    let (_, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000]);
+    let token_client = token::Client::new(&env, &token_address);
+
+    let first = BytesN::from_array(&env, &[1; 32]);
+    client.release_with_ref(&escrow_id, &0, &token_address, &first);
+    assert_eq!(token_client.balance(&recipient), 1000);
+
+    // A retried trigger with the same reference moves nothing
+    client.release_with_ref(&escrow_id, &0, &token_address, &first);
+    assert_eq!(token_client.balance(&recipient), 1000);
+
+    // Reusing the reference for another milestone is an error, not a no-op
+    assert_eq!(
+        client.try_release_with_ref(&escrow_id, &1, &token_address, &first),
+        Err(Ok(Error::DuplicateReference))
+    );
+    assert_eq!(token_client.balance(&recipient), 1000);
+    assert_eq!(
+        client
+            .get_escrow(&escrow_id)
+            .milestones
+            .get(1)
+            .unwrap()
+            .status,
+        MilestoneStatus::Pending
+    );
+
+    // A fresh reference releases normally, and failures leave it unused
+    let second = BytesN::from_array(&env, &[2; 32]);
+    assert_eq!(
+        client.try_release_with_ref(&escrow_id, &0, &token_address, &second),
+        Err(Ok(Error::MilestoneAlreadyReleased))
+    );
+    client.release_with_ref(&escrow_id, &1, &token_address, &second);
+    assert_eq!(token_client.balance(&recipient), 3000);
+}
+
+#[test]
+fn test_export_escrow_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let escrow_id = 137u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000, 2000, 3000]);
+
+    env.ledger().set_timestamp(1_700_000_000);
+    client.release_milestone(&escrow_id, &0, &token_address);
+    let reason = String::from_str(&env, "scope dispute");
+    client.dispute_milestone(&escrow_id, &2, &depositor, &reason);
+
+    let export = client.export_escrow(&escrow_id);
+    assert_eq!(export.escrow_id, escrow_id);
+    assert_eq!(export.escrow.recipient, recipient);
+    assert_eq!(
+        export.milestone_statuses,
+        vec![
+            &env,
+            MilestoneStatus::Released,
+            MilestoneStatus::Pending,
+            MilestoneStatus::Disputed
+        ]
+    );
+    assert!(export.funded);
+    assert_eq!(export.balance, 5000);
+    assert_eq!(export.disputes.len(), 1);
+    let (index, dispute) = export.disputes.get(0).unwrap();
+    assert_eq!(index, 2);
+    assert_eq!(dispute.disputer, depositor);
+    assert_eq!(export.exported_at, 1_700_000_000);
+
+    assert_eq!(
+        client.try_export_escrow(&999).err(),
+        Some(Ok(Error::EscrowNotFound))
+    );
+}
+
+#[test]
+fn test_export_escrow_funded_flag_after_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    // Funded, then cancelled with nothing released
+    let escrow_id = 162u64;
+    let (depositor, recipient, token_address) =
+        setup_funded_escrow(&env, &client, escrow_id, &[1000]);
+    client.cancel_escrow(&escrow_id, &None);
+
+    let export = client.export_escrow(&escrow_id);
+    assert_eq!(export.escrow.status, EscrowStatus::Cancelled);
+    assert!(export.funded);
+    assert_eq!(export.balance, 0);
+    assert_eq!(export.escrow.total_refunded, 1000);
+
+    // Cancelled before funding
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &163u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.cancel_escrow(&163u64, &None);
+    assert!(!client.export_escrow(&163u64).funded);
+}
+
+#[test]
+fn test_initialize_twice_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+
+    let attacker = Address::generate(&env);
+    assert_eq!(
+        client.try_initialize(&attacker, &Some(0)),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let config = client.config();
+    assert_eq!(config.admin, Some(treasury.clone()));
+    assert_eq!(config.fee_collector, Some(treasury));
+    assert_eq!(config.fee_bps, 100);
+}
+
+#[test]
+fn test_total_fees_collected_per_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+
+    let escrow_id = 138u64;
+    let (_, _, token_address) = setup_funded_escrow(&env, &client, escrow_id, &[1000, 3000]);
+    let (_, _, other_token) = setup_funded_escrow(&env, &client, 139, &[5000]);
+    assert_eq!(client.total_fees_collected(&token_address), 0);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(client.total_fees_collected(&token_address), 10);
+    client.release_all(&escrow_id);
+    assert_eq!(client.total_fees_collected(&token_address), 40);
+
+    // Totals are kept per token
+    assert_eq!(client.total_fees_collected(&other_token), 0);
+    client.release_milestone(&139, &0, &other_token);
+    assert_eq!(client.total_fees_collected(&other_token), 50);
+    assert_eq!(client.total_fees_collected(&token_address), 40);
+
+    // Vesting claims pay the fee too
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (token_admin, vesting_token) = create_test_token(&env, &Address::generate(&env));
+    token_admin.mint(&depositor, &2000);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 2000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Vest"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    client.create_escrow(
+        &160u64,
+        &depositor,
+        &recipient,
+        &vesting_token,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_vesting(
+        &160u64,
+        &VestingSchedule {
+            start: 1_000,
+            cliff: 0,
+            duration: 1_000,
+        },
+    );
+    token::Client::new(&env, &vesting_token).approve(&depositor, &contract_id, &2000, &200);
+    client.deposit_funds(&160u64);
+
+    env.ledger().set_timestamp(2_000);
+    client.claim_vested(&160u64);
+    assert_eq!(client.total_fees_collected(&vesting_token), 20);
+}
+
+#[contract]
+struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, asset: Symbol, price: i128) {
+        env.storage().instance().set(&asset, &price);
+    }
+
+    pub fn price(env: Env, asset: Symbol) -> i128 {
+        env.storage().instance().get(&asset).unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_price_condition_gates_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &1000);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Hedge"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    let escrow_id = 140u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_price_condition(
+        &escrow_id,
+        &Some(PriceCondition {
+            oracle: oracle_id.clone(),
+            asset: symbol_short!("XLM"),
+            threshold: 500,
+            above: true,
+        }),
+    );
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Below the threshold the release is refused
+    oracle.set_price(&symbol_short!("XLM"), &499);
+    assert!(!client.is_releasable(&escrow_id, &0));
+    assert_eq!(
+        client.try_release_milestone(&escrow_id, &0, &token_address),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+
+    oracle.set_price(&symbol_short!("XLM"), &500);
+    client.release_milestone(&escrow_id, &0, &token_address);
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_extend_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let token_address = Address::generate(&env);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Build"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+    let escrow_id = 141u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    client.extend_deadline(&escrow_id, &1706500000u64, &recipient);
+    assert_eq!(client.get_escrow(&escrow_id).deadline, 1706500000);
+
+    // Shortening or keeping the same deadline is rejected
+    let result = client.try_extend_deadline(&escrow_id, &1706450000u64, &depositor);
+    assert_eq!(result, Err(Ok(Error::InvalidDeadline)));
+    let result = client.try_extend_deadline(&escrow_id, &1706500000u64, &depositor);
+    assert_eq!(result, Err(Ok(Error::InvalidDeadline)));
+
+    // Only the participants may extend
+    let result = client.try_extend_deadline(&escrow_id, &1706600000u64, &outsider);
+    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+
+    client.extend_deadline(&escrow_id, &1706600000u64, &depositor);
+    assert_eq!(client.get_escrow(&escrow_id).deadline, 1706600000);
+}
+
+#[test]
+fn test_open_requests_are_capped_per_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let recipient = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Invoice"),
+            depends_on: None,
+            released_at: None,
+            recipient: None,
+            deadline: None,
+            metadata_uri: None,
+        },
+    ];
+
+    for escrow_id in 146u64..156 {
+        client.request_escrow(
+            &escrow_id,
+            &recipient,
+            &depositor,
+            &token_address,
+            &milestones,
+            &1706400000u64,
+        );
+    }
+
+    let result = client.try_request_escrow(
+        &156u64,
+        &recipient,
+        &depositor,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::TooManyOpenRequests)));
+
+    // Closing a request frees a slot
+    client.decline_escrow(&146, &recipient);
+    client.request_escrow(
+        &156u64,
+        &recipient,
+        &depositor,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+}
+
+#[test]
+fn test_complete_escrow_rejects_cancelled_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let escrow_id = 157u64;
+    setup_funded_escrow(&env, &client, escrow_id, &[500]);
+
+    // Every milestone is settled, but the escrow itself was cancelled
+    client.mutual_cancel_milestone(&escrow_id, &0);
+    client.cancel_escrow(&escrow_id, &None);
+
+    let result = client.try_complete_escrow(&escrow_id);
+    assert_eq!(result, Err(Ok(Error::EscrowNotActive)));
+    assert_eq!(
+        client.get_escrow(&escrow_id).status,
+        EscrowStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_release_all_respects_release_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 158u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let milestone = Milestone {
+        amount: 1000,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Phase"),
+        depends_on: None,
+        released_at: None,
+        recipient: None,
+        deadline: None,
+        metadata_uri: None,
+    };
+    let milestones = vec![&env, milestone.clone(), milestone.clone(), milestone];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    client.set_release_cooldown(&escrow_id, &5);
+    token_client.approve(&depositor, &contract_id, &3000, &1000);
+    client.deposit_funds(&escrow_id);
+
+    // Draining several milestones in one call would skip the cooldown
+    let result = client.try_release_all(&escrow_id);
+    assert_eq!(result, Err(Ok(Error::ActionTooSoon)));
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    client.release_milestone(&escrow_id, &0, &token_address);
+    env.ledger().set_sequence_number(105);
+    client.release_milestone(&escrow_id, &1, &token_address);
+
+    // Once only one milestone is left, release_all behaves like a release
+    let result = client.try_release_all(&escrow_id);
+    assert_eq!(result, Err(Ok(Error::ActionTooSoon)));
+    env.ledger().set_sequence_number(110);
+    client.release_all(&escrow_id);
+    assert_eq!(token_client.balance(&recipient), 3000);
+    assert_eq!(
+        client.get_escrow(&escrow_id).status,
+        EscrowStatus::Completed
+    );
+}
+
+#[test]
+fn test_dispute_resolution_is_exempt_from_release_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(0));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 159u64;
+
+    let (token_admin, token_address) = create_test_token(&env, &admin);
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let milestone = Milestone {
+        amount: 1000,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Phase"),
+        depends_on: None,
+        released_at: None,
+        recipient: None,
+        deadline: None,
+        metadata_uri: None,
+    };
+    let milestones = vec![&env, milestone.clone(), milestone.clone(), milestone];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    let arbiter = Address::generate(&env);
+    client.set_arbiter(&escrow_id, &arbiter, &3000);
+    client.set_release_cooldown(&escrow_id, &5);
+    token_client.approve(&depositor, &contract_id, &3000, &1000);
+    client.deposit_funds(&escrow_id);
+
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    client.dispute_milestone(
+        &escrow_id,
+        &1,
+        &recipient,
+        &String::from_str(&env, "Unpaid"),
+    );
+
+    // Depositor-driven payouts wait out the cooldown
+    let result = client.try_confirm_delivery(&escrow_id, &2, &depositor);
+    assert_eq!(result, Err(Ok(Error::ActionTooSoon)));
+
+    // The arbiter's ruling is paid out straight away
+    client.resolve_dispute(&escrow_id, &1, &true);
+    assert_eq!(token_client.balance(&recipient), 2000);
+}
+
+#[test]
+fn test_delivery_and_resolution_payouts_charge_platform_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(VaultixEscrow, ());
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    // 1% platform fee
+    let treasury = Address::generate(&env);
+    client.initialize(&treasury, &Some(100));
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let (token_admin, token_address) = create_test_token(&env, &Address::generate(&env));
+    let token_client = token::Client::new(&env, &token_address);
+    token_admin.mint(&depositor, &3000);
+
+    let milestone = |amount: i128| Milestone {
+        amount,
+        status: MilestoneStatus::Pending,
+        description: symbol_short!("Task"),
+        depends_on: None,
+        released_at: None,
+        recipient: None,
+        deadline: None,
+        metadata_uri: None,
+    };
+    let escrow_id = 161u64;
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &vec![&env, milestone(1000), milestone(2000)],
+        &1706400000u64,
+    );
+    client.set_arbiter(&escrow_id, &arbiter, &2000);
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // The recipient nets the milestone less the fee on a confirmed delivery
+    client.confirm_delivery(&escrow_id, &0, &depositor);
+    assert_eq!(token_client.balance(&recipient), 990);
+    assert_eq!(token_client.balance(&treasury), 10);
+
+    // ... and on a dispute resolved in their favour
+    let reason = String::from_str(&env, "late");
+    client.dispute_milestone(&escrow_id, &1, &depositor, &reason);
+    client.resolve_dispute(&escrow_id, &1, &true);
+    assert_eq!(token_client.balance(&recipient), 2970);
+    assert_eq!(token_client.balance(&treasury), 30);
+    assert_eq!(client.total_fees_collected(&token_address), 30);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}